@@ -48,19 +48,18 @@ pub async fn list_drives() -> Result<Vec<DriveInfo>, String> {
 
 #[cfg(target_os = "linux")]
 async fn list_drives_linux() -> Result<Vec<DriveInfo>, String> {
-    let output = Command::new("lsblk")
+    match Command::new("lsblk")
         .args(["-J", "-b", "-o", "NAME,SIZE,TYPE,MOUNTPOINT,MODEL,VENDOR,SERIAL,RM,TRAN,LABEL,FSTYPE"])
         .output()
-        .map_err(|e| format!("Failed to run lsblk: {}", e))?;
-    
-    if !output.status.success() {
-        return Err(format!(
-            "lsblk failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        ));
+    {
+        Ok(output) if output.status.success() => list_drives_linux_lsblk(&output.stdout),
+        _ => list_drives_linux_sysfs(),
     }
-    
-    let json_str = String::from_utf8_lossy(&output.stdout);
+}
+
+#[cfg(target_os = "linux")]
+fn list_drives_linux_lsblk(stdout: &[u8]) -> Result<Vec<DriveInfo>, String> {
+    let json_str = String::from_utf8_lossy(stdout);
     let lsblk: LsblkOutput = serde_json::from_str(&json_str)
         .map_err(|e| format!("Failed to parse lsblk output: {}", e))?;
     
@@ -131,10 +130,169 @@ async fn list_drives_linux() -> Result<Vec<DriveInfo>, String> {
     Ok(drives)
 }
 
+/// Fallback enumeration used when `lsblk` isn't installed: walk `/sys/block`
+/// directly and resolve mount state from `/proc/self/mountinfo`, the way
+/// coreos-installer inspects block devices without shelling out.
+#[cfg(target_os = "linux")]
+fn list_drives_linux_sysfs() -> Result<Vec<DriveInfo>, String> {
+    use std::fs;
+
+    let mountinfo = parse_mountinfo();
+    let mut drives = Vec::new();
+
+    let entries = fs::read_dir("/sys/block")
+        .map_err(|e| format!("Failed to read /sys/block: {}", e))?;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // Skip loop devices, CD-ROMs, and ram disks; these aren't flashable targets.
+        if name.starts_with("loop") || name.starts_with("sr") || name.starts_with("ram") {
+            continue;
+        }
+
+        let sys_path = entry.path();
+
+        let sectors: u64 = fs::read_to_string(sys_path.join("size"))
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(0);
+        let size = sectors * 512;
+
+        let is_removable = fs::read_to_string(sys_path.join("removable"))
+            .ok()
+            .map(|s| s.trim() == "1")
+            .unwrap_or(false);
+
+        let model = fs::read_to_string(sys_path.join("device/model"))
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        let vendor = fs::read_to_string(sys_path.join("device/vendor"))
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+
+        let bus_type = detect_bus_type(&sys_path);
+        let is_usb = bus_type == "usb";
+
+        let device = format!("/dev/{}", name);
+        let mut mount_points = Vec::new();
+        let mut partitions = Vec::new();
+        let mut is_system = false;
+
+        for part_entry in fs::read_dir(&sys_path).into_iter().flatten().flatten() {
+            let part_name = part_entry.file_name().to_string_lossy().to_string();
+            if !part_name.starts_with(&name) || !part_entry.path().join("partition").exists() {
+                continue;
+            }
+
+            let part_device = format!("/dev/{}", part_name);
+            let mount_point = mountinfo.get(&part_device).cloned();
+
+            if let Some(mp) = &mount_point {
+                if mp == "/" || mp == "/boot" || mp == "/home" || mp.starts_with("/boot") {
+                    is_system = true;
+                }
+                mount_points.push(mp.clone());
+            }
+
+            let part_sectors: u64 = fs::read_to_string(part_entry.path().join("size"))
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(0);
+
+            partitions.push(PartitionInfo {
+                device: part_device,
+                label: String::new(),
+                filesystem: String::new(),
+                size: part_sectors * 512,
+                mount_point,
+            });
+        }
+
+        if is_system {
+            continue;
+        }
+
+        drives.push(DriveInfo {
+            device,
+            name: name.clone(),
+            size,
+            size_human: format_size(size),
+            model,
+            vendor,
+            serial: String::new(),
+            is_removable,
+            is_usb,
+            mount_points,
+            partitions,
+            bus_type,
+        });
+    }
+
+    Ok(drives)
+}
+
+/// Resolve a block device's transport (usb/ata/nvme/...) by following the
+/// `device` symlink under `/sys/block/<dev>` back to its bus subsystem.
+#[cfg(target_os = "linux")]
+fn detect_bus_type(sys_path: &std::path::Path) -> String {
+    let Ok(canonical) = std::fs::canonicalize(sys_path.join("device")) else {
+        return "unknown".to_string();
+    };
+    let path_str = canonical.to_string_lossy();
+
+    if path_str.contains("/usb") {
+        "usb".to_string()
+    } else if path_str.contains("/nvme") {
+        "nvme".to_string()
+    } else if path_str.contains("/ata") {
+        "ata".to_string()
+    } else if path_str.contains("/mmc") {
+        "mmc".to_string()
+    } else {
+        "unknown".to_string()
+    }
+}
+
+/// Map each mounted device node to its mount point by parsing
+/// `/proc/self/mountinfo`, used as the sysfs-path fallback's source of mount
+/// state instead of `lsblk`'s own MOUNTPOINT column.
+#[cfg(target_os = "linux")]
+fn parse_mountinfo() -> std::collections::HashMap<String, String> {
+    let mut map = std::collections::HashMap::new();
+
+    let Ok(contents) = std::fs::read_to_string("/proc/self/mountinfo") else {
+        return map;
+    };
+
+    for line in contents.lines() {
+        // Format: ID PARENT MAJOR:MINOR ROOT MOUNTPOINT OPTIONS - FSTYPE SOURCE SUPER_OPTIONS
+        let Some(dash_pos) = line.find(" - ") else { continue };
+        let (left, right) = line.split_at(dash_pos);
+        let left_fields: Vec<&str> = left.split_whitespace().collect();
+        let right_fields: Vec<&str> = right.trim_start_matches(" - ").split_whitespace().collect();
+
+        if left_fields.len() < 5 || right_fields.len() < 2 {
+            continue;
+        }
+
+        let mount_point = left_fields[4];
+        let source = right_fields[1];
+
+        if source.starts_with("/dev/") {
+            map.insert(source.to_string(), mount_point.to_string());
+        }
+    }
+
+    map
+}
+
 #[cfg(target_os = "windows")]
 async fn list_drives_windows() -> Result<Vec<DriveInfo>, String> {
     use std::process::Command;
-    
+
     // Use PowerShell to get drive information
     let ps_script = r#"
         Get-Disk | Where-Object { $_.BusType -eq 'USB' -or $_.IsSystem -eq $false } | ForEach-Object {
@@ -157,24 +315,132 @@ async fn list_drives_windows() -> Result<Vec<DriveInfo>, String> {
             }
         } | ConvertTo-Json -Depth 3
     "#;
-    
+
     let output = Command::new("powershell")
         .args(["-Command", ps_script])
         .output()
         .map_err(|e| format!("Failed to run PowerShell: {}", e))?;
-    
+
     if !output.status.success() {
         return Err(format!(
             "PowerShell failed: {}",
             String::from_utf8_lossy(&output.stderr)
         ));
     }
-    
-    // Parse JSON output and convert to DriveInfo
+
     let json_str = String::from_utf8_lossy(&output.stdout);
-    
-    // For now, return a placeholder - full Windows implementation would parse the JSON
-    Ok(Vec::new())
+    let trimmed = json_str.trim();
+    if trimmed.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // PowerShell's ConvertTo-Json emits a bare object (not an array) when
+    // there's exactly one result, so normalize to an array before parsing.
+    let disks: Vec<WindowsDisk> = if trimmed.starts_with('[') {
+        serde_json::from_str(trimmed)
+            .map_err(|e| format!("Failed to parse PowerShell disk JSON: {}", e))?
+    } else {
+        let single: WindowsDisk = serde_json::from_str(trimmed)
+            .map_err(|e| format!("Failed to parse PowerShell disk JSON: {}", e))?;
+        vec![single]
+    };
+
+    Ok(disks.into_iter().map(WindowsDisk::into_drive_info).collect())
+}
+
+#[cfg(target_os = "windows")]
+#[derive(Debug, Deserialize)]
+struct WindowsDisk {
+    #[serde(rename = "Number")]
+    number: u32,
+    #[serde(rename = "Size")]
+    size: Option<u64>,
+    #[serde(rename = "Model")]
+    model: Option<String>,
+    #[serde(rename = "SerialNumber")]
+    serial_number: Option<String>,
+    #[serde(rename = "BusType")]
+    bus_type: Option<String>,
+    #[serde(rename = "IsRemovable")]
+    is_removable: Option<bool>,
+    #[serde(rename = "Partitions")]
+    partitions: Option<WindowsPartitions>,
+}
+
+/// `Get-Partition | ForEach-Object { ... }` has the same single-object vs.
+/// array quirk as the top-level disk list when a disk has exactly one partition.
+#[cfg(target_os = "windows")]
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum WindowsPartitions {
+    One(WindowsPartition),
+    Many(Vec<WindowsPartition>),
+}
+
+#[cfg(target_os = "windows")]
+impl WindowsPartitions {
+    fn into_vec(self) -> Vec<WindowsPartition> {
+        match self {
+            WindowsPartitions::One(p) => vec![p],
+            WindowsPartitions::Many(ps) => ps,
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+#[derive(Debug, Deserialize)]
+struct WindowsPartition {
+    #[serde(rename = "DriveLetter")]
+    drive_letter: Option<String>,
+    #[serde(rename = "Size")]
+    size: Option<u64>,
+    #[serde(rename = "Type")]
+    partition_type: Option<String>,
+}
+
+#[cfg(target_os = "windows")]
+impl WindowsDisk {
+    fn into_drive_info(self) -> DriveInfo {
+        let bus_type = self.bus_type.unwrap_or_else(|| "unknown".to_string());
+        let is_usb = bus_type.eq_ignore_ascii_case("usb");
+        let size = self.size.unwrap_or(0);
+
+        let partitions: Vec<PartitionInfo> = self.partitions
+            .map(|p| p.into_vec())
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| PartitionInfo {
+                device: p.drive_letter
+                    .map(|l| format!("{}:", l))
+                    .unwrap_or_else(|| format!("Partition{}", i)),
+                label: String::new(),
+                filesystem: p.partition_type.unwrap_or_default(),
+                size: p.size.unwrap_or(0),
+                mount_point: None,
+            })
+            .collect();
+
+        let mount_points = partitions.iter()
+            .map(|p| p.device.clone())
+            .filter(|d| d.ends_with(':'))
+            .collect();
+
+        DriveInfo {
+            device: format!("\\\\.\\PhysicalDrive{}", self.number),
+            name: format!("Disk {}", self.number),
+            size,
+            size_human: format_size(size),
+            model: self.model.unwrap_or_default().trim().to_string(),
+            vendor: String::new(),
+            serial: self.serial_number.unwrap_or_default().trim().to_string(),
+            is_removable: self.is_removable.unwrap_or(is_usb),
+            is_usb,
+            mount_points,
+            partitions,
+            bus_type,
+        }
+    }
 }
 
 #[cfg(target_os = "macos")]
@@ -183,16 +449,127 @@ async fn list_drives_macos() -> Result<Vec<DriveInfo>, String> {
         .args(["list", "-plist"])
         .output()
         .map_err(|e| format!("Failed to run diskutil: {}", e))?;
-    
+
     if !output.status.success() {
         return Err(format!(
             "diskutil failed: {}",
             String::from_utf8_lossy(&output.stderr)
         ));
     }
-    
-    // For now, return a placeholder - full macOS implementation would parse the plist
-    Ok(Vec::new())
+
+    let root: plist::Dictionary = plist::from_bytes(&output.stdout)
+        .map_err(|e| format!("Failed to parse diskutil plist: {}", e))?;
+
+    let Some(disks) = root.get("AllDisksAndPartitions").and_then(|v| v.as_array()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut drives = Vec::new();
+
+    for disk in disks {
+        let Some(disk) = disk.as_dictionary() else { continue };
+
+        let device = disk.get("DeviceIdentifier")
+            .and_then(|v| v.as_string())
+            .unwrap_or_default();
+        if device.is_empty() {
+            continue;
+        }
+
+        // Only whole disks look like "diskN" (no "sM" partition suffix).
+        let is_whole_disk = device.strip_prefix("disk")
+            .map(|rest| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit()))
+            .unwrap_or(false);
+        if !is_whole_disk {
+            continue;
+        }
+
+        let size = disk.get("Size").and_then(|v| v.as_unsigned_integer()).unwrap_or(0);
+        let media_name = disk.get("MediaName")
+            .and_then(|v| v.as_string())
+            .unwrap_or("Unknown")
+            .to_string();
+
+        let info = macos_disk_info(&device);
+        let is_removable = info.as_ref().map(|i| i.removable).unwrap_or(false);
+        let is_usb = info.as_ref().map(|i| i.protocol.eq_ignore_ascii_case("usb")).unwrap_or(false);
+        let bus_type = info.map(|i| i.protocol).unwrap_or_else(|| "unknown".to_string());
+
+        let mut mount_points = Vec::new();
+        let mut partitions = Vec::new();
+
+        if let Some(parts) = disk.get("Partitions").and_then(|v| v.as_array()) {
+            for part in parts {
+                let Some(part) = part.as_dictionary() else { continue };
+                let part_device = part.get("DeviceIdentifier")
+                    .and_then(|v| v.as_string())
+                    .unwrap_or_default()
+                    .to_string();
+                let mount_point = part.get("MountPoint").and_then(|v| v.as_string()).map(|s| s.to_string());
+
+                if let Some(mp) = &mount_point {
+                    mount_points.push(mp.clone());
+                }
+
+                partitions.push(PartitionInfo {
+                    device: format!("/dev/{}", part_device),
+                    label: part.get("VolumeName").and_then(|v| v.as_string()).unwrap_or_default().to_string(),
+                    filesystem: part.get("Content").and_then(|v| v.as_string()).unwrap_or_default().to_string(),
+                    size: part.get("Size").and_then(|v| v.as_unsigned_integer()).unwrap_or(0),
+                    mount_point,
+                });
+            }
+        }
+
+        drives.push(DriveInfo {
+            device: format!("/dev/{}", device),
+            name: media_name,
+            size,
+            size_human: format_size(size),
+            model: String::new(),
+            vendor: String::new(),
+            serial: String::new(),
+            is_removable,
+            is_usb,
+            mount_points,
+            partitions,
+            bus_type,
+        });
+    }
+
+    Ok(drives)
+}
+
+#[cfg(target_os = "macos")]
+struct MacosDiskInfo {
+    removable: bool,
+    protocol: String,
+}
+
+/// Fetch the Removable/Internal and protocol (USB/SATA/...) flags for a
+/// single disk via `diskutil info -plist`.
+#[cfg(target_os = "macos")]
+fn macos_disk_info(device: &str) -> Option<MacosDiskInfo> {
+    let output = Command::new("diskutil")
+        .args(["info", "-plist", device])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let info: plist::Dictionary = plist::from_bytes(&output.stdout).ok()?;
+    let removable = info.get("RemovableMedia")
+        .and_then(|v| v.as_boolean())
+        .unwrap_or_else(|| {
+            info.get("Internal").and_then(|v| v.as_boolean()).map(|internal| !internal).unwrap_or(false)
+        });
+    let protocol = info.get("BusProtocol")
+        .and_then(|v| v.as_string())
+        .unwrap_or("unknown")
+        .to_string();
+
+    Some(MacosDiskInfo { removable, protocol })
 }
 
 #[derive(Debug, Deserialize)]
@@ -4,6 +4,8 @@
 //! - LUKS/LUKS2 encryption (Linux)
 //! - VeraCrypt-compatible encryption
 //! - AES-256-GCM for file-level encryption
+//! - A streaming AES-256-GCM image writer ([`crate::encrypted_stream`]) that
+//!   needs no external encryption tool at all
 
 use serde::{Deserialize, Serialize};
 use aes_gcm::{
@@ -140,12 +142,13 @@ impl LuksHeader {
     }
 }
 
-/// VeraCrypt volume header (simplified)
+/// VeraCrypt volume header fields (the plaintext side of the metadata; the
+/// on-disk salt, XTS encryption, and CRC handling live in [`crate::veracrypt`]
+/// since they depend on the passphrase and aren't meaningful on their own).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VeraCryptHeader {
     pub version: u16,
     pub required_program_version: u16,
-    pub crc32: u32,
     pub volume_creation_time: u64,
     pub header_creation_time: u64,
     pub hidden_volume_size: u64,
@@ -156,9 +159,6 @@ pub struct VeraCryptHeader {
     pub sector_size: u32,
     pub encryption_algorithm: u32,
     pub hash_algorithm: u32,
-    pub master_key: [u8; 64],
-    pub secondary_key: [u8; 64],
-    pub salt: [u8; 64],
 }
 
 impl VeraCryptHeader {
@@ -167,32 +167,20 @@ impl VeraCryptHeader {
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        
-        let mut master_key = [0u8; 64];
-        let mut secondary_key = [0u8; 64];
-        let mut salt = [0u8; 64];
-        
-        rand::thread_rng().fill_bytes(&mut master_key);
-        rand::thread_rng().fill_bytes(&mut secondary_key);
-        rand::thread_rng().fill_bytes(&mut salt);
-        
+
         Self {
             version: 5,
             required_program_version: 0x10b,
-            crc32: 0,
             volume_creation_time: now,
             header_creation_time: now,
             hidden_volume_size: 0,
             volume_size,
             encrypted_area_start: 131072, // 128KB offset
-            encrypted_area_length: volume_size - 131072,
+            encrypted_area_length: volume_size.saturating_sub(131072),
             flags: 0,
             sector_size: 512,
             encryption_algorithm: encryption_algo,
             hash_algorithm: hash_algo,
-            master_key,
-            secondary_key,
-            salt,
         }
     }
 }
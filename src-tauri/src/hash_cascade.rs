@@ -0,0 +1,303 @@
+//! Bloom filter cascade for compact image-hash allow/deny lists
+//!
+//! A curated "known-good" or "known-revoked" image hash set can run to
+//! megabytes of plaintext SHA-256 hex strings. A multi-level Bloom filter
+//! cascade - the structure behind Firefox's `cert_storage` (`rust_cascade`)
+//! - encodes an exact answer for that whole known universe in a fraction of
+//! the space, at the cost of needing a handful of extra filter levels
+//! instead of one.
+//!
+//! Construction: level 0 is a Bloom filter holding every hash in the
+//! "included" set. Any "excluded" hash that's a false positive against
+//! level 0 - the filter can't tell it apart from a real included hash - is
+//! collected and a level 1 filter is built over just those. Any "included"
+//! hash that's in turn a false positive against level 1 goes into a level 2
+//! filter, and so on, alternating sets, until a level produces no false
+//! positives and the cascade terminates.
+//!
+//! Querying walks the levels from 0: the first level a hash is *absent*
+//! from gives the answer - even depth means excluded (level 0 is exactly
+//! the included set, and Bloom filters never false-negative), odd depth
+//! means included (the previous level's tentative membership survived this
+//! level's false-positive filter unscathed). A hash present at every level
+//! all the way down is decided by the last level's own parity, since the
+//! cascade only ever terminates once a level has zero false positives left
+//! to resolve.
+//!
+//! [`HashCascade::to_bytes`]/[`HashCascade::from_bytes`] give it a small
+//! on-disk format, all integers little-endian:
+//!
+//! ```text
+//! magic "N1DHCAS1" (8 bytes), level count (u32)
+//! then per level: num_bits (u64), num_hashes (u32),
+//!                 bitset length in bytes (u32), bitset bytes
+//! ```
+//!
+//! [`load_hash_cascade`] reads that format back
+//! with a plain `std::fs::read` rather than an actual `mmap` - there's no
+//! memory-mapping crate in this tree, so a full read is the honest
+//! substitute; the format is unchanged either way.
+
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// A single Bloom filter level: `num_hashes` independent bit positions per
+/// element, derived from one SHA-256 digest via Kirsch-Mitzenmacher
+/// double hashing instead of `num_hashes` separate hash functions.
+#[derive(Debug, Clone)]
+struct BloomFilter {
+    bits: Vec<u8>,
+    num_bits: u64,
+    num_hashes: u32,
+}
+
+impl BloomFilter {
+    /// Size the filter for `num_items` elements at `false_positive_rate`
+    /// using the standard optimal-m/optimal-k formulas.
+    fn with_capacity(num_items: usize, false_positive_rate: f64) -> Self {
+        let n = (num_items.max(1)) as f64;
+        let m = ((-n * false_positive_rate.ln()) / std::f64::consts::LN_2.powi(2))
+            .ceil()
+            .max(8.0) as u64;
+        let k = ((m as f64 / n) * std::f64::consts::LN_2).round().max(1.0) as u32;
+
+        Self {
+            bits: vec![0u8; ((m + 7) / 8) as usize],
+            num_bits: m,
+            num_hashes: k,
+        }
+    }
+
+    fn hash_pair(data: &[u8]) -> (u64, u64) {
+        let digest = Sha256::digest(data);
+        let h1 = u64::from_le_bytes(digest[0..8].try_into().unwrap());
+        let h2 = u64::from_le_bytes(digest[8..16].try_into().unwrap());
+        (h1, h2)
+    }
+
+    fn bit_indices(&self, data: &[u8]) -> Vec<u64> {
+        let (h1, h2) = Self::hash_pair(data);
+        (0..self.num_hashes as u64)
+            .map(|i| h1.wrapping_add(i.wrapping_mul(h2)) % self.num_bits)
+            .collect()
+    }
+
+    fn insert(&mut self, data: &[u8]) {
+        for idx in self.bit_indices(data) {
+            let byte = (idx / 8) as usize;
+            let bit = (idx % 8) as u32;
+            self.bits[byte] |= 1 << bit;
+        }
+    }
+
+    fn contains(&self, data: &[u8]) -> bool {
+        self.bit_indices(data)
+            .into_iter()
+            .all(|idx| (self.bits[(idx / 8) as usize] >> (idx % 8)) & 1 == 1)
+    }
+}
+
+/// The result of checking a hash against a loaded [`HashCascade`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Classification {
+    Included,
+    Excluded,
+    Unknown,
+}
+
+/// A Bloom filter cascade over one known universe of image hashes.
+#[derive(Debug, Clone, Default)]
+pub struct HashCascade {
+    levels: Vec<BloomFilter>,
+}
+
+const CASCADE_MAGIC: &[u8; 8] = b"N1DHCAS1";
+
+impl HashCascade {
+    /// Build a cascade from a set of known-good (`included`) and
+    /// known-bad/revoked (`excluded`) hex hash strings.
+    pub fn build(included: &[String], excluded: &[String], false_positive_rate: f64) -> Self {
+        let mut levels = Vec::new();
+        let mut current_set = included.to_vec();
+        let mut other_set = excluded.to_vec();
+        let mut set_is_included = true;
+
+        while !current_set.is_empty() {
+            let mut filter = BloomFilter::with_capacity(current_set.len(), false_positive_rate);
+            for item in &current_set {
+                filter.insert(normalize(item).as_bytes());
+            }
+
+            let false_positives: Vec<String> = other_set
+                .iter()
+                .filter(|item| filter.contains(normalize(item).as_bytes()))
+                .cloned()
+                .collect();
+
+            levels.push(filter);
+
+            if false_positives.is_empty() {
+                break;
+            }
+
+            current_set = false_positives;
+            other_set = if set_is_included { included.to_vec() } else { excluded.to_vec() };
+            set_is_included = !set_is_included;
+        }
+
+        Self { levels }
+    }
+
+    /// Classify a hex hash string against this cascade.
+    pub fn classify(&self, hash: &str) -> Classification {
+        if self.levels.is_empty() {
+            return Classification::Unknown;
+        }
+
+        let needle = normalize(hash);
+        for (depth, filter) in self.levels.iter().enumerate() {
+            if !filter.contains(needle.as_bytes()) {
+                return if depth % 2 == 0 { Classification::Excluded } else { Classification::Included };
+            }
+        }
+
+        // Present at every level: the final level's own parity decides,
+        // since the cascade only stops once a level leaves no false
+        // positives left to resolve.
+        if self.levels.len() % 2 == 1 { Classification::Included } else { Classification::Excluded }
+    }
+
+    /// Serialize to the on-disk format documented in the module doc-comment.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(CASCADE_MAGIC);
+        buf.extend_from_slice(&(self.levels.len() as u32).to_le_bytes());
+        for level in &self.levels {
+            buf.extend_from_slice(&level.num_bits.to_le_bytes());
+            buf.extend_from_slice(&level.num_hashes.to_le_bytes());
+            buf.extend_from_slice(&(level.bits.len() as u32).to_le_bytes());
+            buf.extend_from_slice(&level.bits);
+        }
+        buf
+    }
+
+    /// Parse the on-disk format written by [`HashCascade::to_bytes`].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, String> {
+        if buf.len() < 12 || &buf[0..8] != CASCADE_MAGIC {
+            return Err("Not a valid hash cascade file".to_string());
+        }
+
+        let num_levels = u32::from_le_bytes(buf[8..12].try_into().unwrap()) as usize;
+        let mut pos = 12;
+        let mut levels = Vec::with_capacity(num_levels);
+
+        for _ in 0..num_levels {
+            if pos + 16 > buf.len() {
+                return Err("Truncated hash cascade file".to_string());
+            }
+            let num_bits = u64::from_le_bytes(buf[pos..pos + 8].try_into().unwrap());
+            let num_hashes = u32::from_le_bytes(buf[pos + 8..pos + 12].try_into().unwrap());
+            let bits_len = u32::from_le_bytes(buf[pos + 12..pos + 16].try_into().unwrap()) as usize;
+            pos += 16;
+
+            if pos + bits_len > buf.len() {
+                return Err("Truncated hash cascade level bitset".to_string());
+            }
+            let bits = buf[pos..pos + bits_len].to_vec();
+            pos += bits_len;
+
+            levels.push(BloomFilter { bits, num_bits, num_hashes });
+        }
+
+        Ok(Self { levels })
+    }
+}
+
+fn normalize(hash: &str) -> String {
+    hash.trim().to_lowercase()
+}
+
+/// Read a serialized cascade from `path`. See the module doc-comment for
+/// why this is a plain read rather than a true `mmap`.
+pub fn load_hash_cascade(path: &Path) -> Result<HashCascade, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    HashCascade::from_bytes(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_of(n: u32) -> String {
+        hex::encode(Sha256::digest(n.to_le_bytes()))
+    }
+
+    #[test]
+    fn test_included_hashes_classify_as_included() {
+        let included: Vec<String> = (0..200).map(hash_of).collect();
+        let excluded: Vec<String> = (1000..1200).map(hash_of).collect();
+
+        let cascade = HashCascade::build(&included, &excluded, 0.01);
+
+        for hash in &included {
+            assert_eq!(cascade.classify(hash), Classification::Included);
+        }
+    }
+
+    #[test]
+    fn test_excluded_hashes_classify_as_excluded() {
+        let included: Vec<String> = (0..200).map(hash_of).collect();
+        let excluded: Vec<String> = (1000..1200).map(hash_of).collect();
+
+        let cascade = HashCascade::build(&included, &excluded, 0.01);
+
+        for hash in &excluded {
+            assert_eq!(cascade.classify(hash), Classification::Excluded);
+        }
+    }
+
+    #[test]
+    fn test_unrelated_hash_classifies_without_panicking() {
+        let included: Vec<String> = (0..50).map(hash_of).collect();
+        let excluded: Vec<String> = (1000..1050).map(hash_of).collect();
+        let cascade = HashCascade::build(&included, &excluded, 0.01);
+
+        // Not asserting a specific outcome for hashes outside the known
+        // universe (a cascade makes no promises there) - just that it
+        // returns instead of panicking.
+        let _ = cascade.classify(&hash_of(999_999));
+    }
+
+    #[test]
+    fn test_empty_cascade_is_unknown() {
+        let cascade = HashCascade::default();
+        assert_eq!(cascade.classify(&hash_of(1)), Classification::Unknown);
+    }
+
+    #[test]
+    fn test_serialization_roundtrip_preserves_classification() {
+        let included: Vec<String> = (0..100).map(hash_of).collect();
+        let excluded: Vec<String> = (1000..1100).map(hash_of).collect();
+        let cascade = HashCascade::build(&included, &excluded, 0.01);
+
+        let bytes = cascade.to_bytes();
+        let restored = HashCascade::from_bytes(&bytes).unwrap();
+
+        for hash in included.iter().chain(excluded.iter()) {
+            assert_eq!(cascade.classify(hash), restored.classify(hash));
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_bad_magic() {
+        assert!(HashCascade::from_bytes(b"not a cascade file").is_err());
+    }
+
+    #[test]
+    fn test_classify_is_case_insensitive() {
+        let included: Vec<String> = (0..20).map(hash_of).collect();
+        let cascade = HashCascade::build(&included, &[], 0.01);
+
+        assert_eq!(cascade.classify(&included[0].to_uppercase()), Classification::Included);
+    }
+}
@@ -18,8 +18,8 @@ use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Sha512, Digest};
 use md5::Md5;
 use std::fs::File;
-use std::io::{Read, Write, BufReader, BufWriter};
-use std::path::PathBuf;
+use std::io::{Read, Write, Seek, SeekFrom, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
@@ -27,12 +27,25 @@ use tauri::State;
 use tokio::sync::Mutex;
 
 mod encryption;
+mod encrypted_stream;
 mod drives;
 mod secure_erase;
+mod compression;
+mod verification;
+mod veracrypt;
+mod partitioning;
+mod signing;
+mod pe_measure;
+mod hash_cascade;
+mod split_image;
 
-use encryption::{EncryptionConfig, EncryptionType};
+use encryption::{EncryptionConfig, EncryptionType, VeraCryptHeader, veracrypt_hashes};
+use encrypted_stream::{OpeningReader, SealingWriter, StreamHeader, HEADER_SIZE as AES_GCM_HEADER_SIZE};
 use drives::{DriveInfo, list_drives};
-use secure_erase::{SecureEraseMethod, secure_erase_drive};
+use secure_erase::{SecureEraseMethod, ErasureCertificate, secure_erase_drive, secure_erase_drive_with_progress, CancellationToken};
+use compression::{sniff_and_rewind, decoder_for, uncompressed_size_hint, CompressionFormat};
+use verification::{find_sidecar_hash, DigestAlgorithm};
+use split_image::{detect_split_set, total_size};
 
 // ============================================================================
 // State Management
@@ -42,8 +55,12 @@ pub struct AppState {
     pub is_burning: Arc<AtomicBool>,
     pub progress: Arc<AtomicU64>,
     pub total_bytes: Arc<AtomicU64>,
+    pub compressed_bytes_read: Arc<AtomicU64>,
     pub current_operation: Arc<Mutex<String>>,
     pub cancel_flag: Arc<AtomicBool>,
+    pub hash_cascade: Arc<Mutex<Option<hash_cascade::HashCascade>>>,
+    pub blocks_written: Arc<AtomicU64>,
+    pub blocks_skipped: Arc<AtomicU64>,
 }
 
 impl Default for AppState {
@@ -52,8 +69,12 @@ impl Default for AppState {
             is_burning: Arc::new(AtomicBool::new(false)),
             progress: Arc::new(AtomicU64::new(0)),
             total_bytes: Arc::new(AtomicU64::new(0)),
+            compressed_bytes_read: Arc::new(AtomicU64::new(0)),
             current_operation: Arc::new(Mutex::new(String::new())),
             cancel_flag: Arc::new(AtomicBool::new(false)),
+            hash_cascade: Arc::new(Mutex::new(None)),
+            blocks_written: Arc::new(AtomicU64::new(0)),
+            blocks_skipped: Arc::new(AtomicU64::new(0)),
         }
     }
 }
@@ -67,8 +88,13 @@ pub struct BurnConfig {
     pub image_path: String,
     pub target_drive: String,
     pub verify_after_write: bool,
+    pub verify_algorithm: String, // "crc32" or "sha256"
+    pub require_signed_image: bool,
+    pub hash_cascade_gate: bool,
+    pub delta_write: bool,
     pub secure_erase_before: bool,
     pub erase_method: String,
+    pub force_erase: bool,
     pub encryption: Option<EncryptionSettings>,
     pub bootloader: BootloaderConfig,
 }
@@ -107,9 +133,12 @@ pub struct BurnProgress {
     pub progress_percent: f64,
     pub bytes_written: u64,
     pub total_bytes: u64,
+    pub compressed_bytes_read: u64,
     pub speed_mbps: f64,
     pub eta_seconds: u64,
     pub message: String,
+    pub blocks_written: u64,
+    pub blocks_skipped: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -119,6 +148,8 @@ pub struct BurnResult {
     pub hash_verification: Option<HashResult>,
     pub duration_seconds: u64,
     pub bytes_written: u64,
+    pub blocks_written: u64,
+    pub blocks_skipped: u64,
 }
 
 // ============================================================================
@@ -150,19 +181,28 @@ async fn get_drive_info(device: String) -> Result<DriveInfo, String> {
 #[tauri::command]
 async fn get_image_info(path: String) -> Result<ImageInfo, String> {
     let path = PathBuf::from(&path);
-    
+
     if !path.exists() {
         return Err("Image file not found".to_string());
     }
-    
-    let metadata = std::fs::metadata(&path)
-        .map_err(|e| format!("Failed to read file metadata: {}", e))?;
-    
-    let extension = path.extension()
+
+    // A split set's logical extension lives on the part before the numbered
+    // suffix (`image.img.001` -> `img`), so classification below looks past
+    // the suffix rather than seeing `001`/`part1` and calling it Unknown.
+    let (size, part_count, classify_path) = match detect_split_set(&path) {
+        Some(parts) => (total_size(&parts), parts.len() as u32, underlying_path(&path)),
+        None => {
+            let metadata = std::fs::metadata(&path)
+                .map_err(|e| format!("Failed to read file metadata: {}", e))?;
+            (metadata.len(), 1, path.clone())
+        }
+    };
+
+    let extension = classify_path.extension()
         .and_then(|e| e.to_str())
         .unwrap_or("unknown")
         .to_lowercase();
-    
+
     let image_type = match extension.as_str() {
         "iso" => "ISO 9660",
         "img" => "Raw Disk Image",
@@ -173,19 +213,53 @@ async fn get_image_info(path: String) -> Result<ImageInfo, String> {
         "qcow2" => "QEMU Copy-on-Write",
         _ => "Unknown",
     };
-    
+
     Ok(ImageInfo {
         path: path.to_string_lossy().to_string(),
         name: path.file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("unknown")
             .to_string(),
-        size: metadata.len(),
+        size,
         image_type: image_type.to_string(),
         extension,
+        part_count,
     })
 }
 
+/// Strip a split part's trailing numbered suffix so the underlying image
+/// name can be classified by its real extension. The two split-naming
+/// conventions need different treatment: `image.img.001` -> `image.img`
+/// just drops the numbered suffix and its separator, but `image.part1` ->
+/// `image` also has to drop the `part` word itself, since there's no
+/// separator in front of its digits to tell it apart from a real extension.
+/// Whether a separator was actually stripped is exactly that signal - a
+/// base name that legitimately ends in "part" (`bootpart.001`) keeps it,
+/// because its digits were separated from the name by a `.`.
+fn underlying_path(first_part: &Path) -> PathBuf {
+    let Some(file_name) = first_part.file_name().and_then(|n| n.to_str()) else {
+        return first_part.to_path_buf();
+    };
+    let digit_start = split_image::trailing_digit_start(file_name);
+    let prefix = &file_name[..digit_start];
+    let had_separator = prefix.ends_with(['.', '-', '_']);
+    let stem = prefix.trim_end_matches(['.', '-', '_']);
+
+    let stem = if had_separator {
+        stem
+    } else {
+        match split_image::part_word_end(stem) {
+            Some(cut) => stem[..cut].trim_end_matches(['.', '-', '_']),
+            None => stem,
+        }
+    };
+
+    match first_part.parent() {
+        Some(dir) => dir.join(stem),
+        None => PathBuf::from(stem),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageInfo {
     pub path: String,
@@ -193,6 +267,9 @@ pub struct ImageInfo {
     pub size: u64,
     pub image_type: String,
     pub extension: String,
+    /// Number of files backing this image; `1` for a normal single-file
+    /// image, greater when [`detect_split_set`] recognized a split set.
+    pub part_count: u32,
 }
 
 #[tauri::command]
@@ -207,21 +284,28 @@ async fn calculate_hash(
         return Err("File not found".to_string());
     }
     
-    let file = File::open(&path)
-        .map_err(|e| format!("Failed to open file: {}", e))?;
-    
-    let file_size = file.metadata()
-        .map_err(|e| format!("Failed to get file size: {}", e))?
-        .len();
-    
+    let (source, file_size) = split_image::open_image(path.to_string_lossy().as_ref())?;
+
     state.total_bytes.store(file_size, Ordering::SeqCst);
     state.progress.store(0, Ordering::SeqCst);
-    
-    let mut reader = BufReader::with_capacity(1024 * 1024, file);
+
+    let mut reader = BufReader::with_capacity(1024 * 1024, source);
     let mut buffer = vec![0u8; 1024 * 1024];
     let mut bytes_read = 0u64;
     
     let hash = match algorithm.to_lowercase().as_str() {
+        "crc32" => {
+            let mut hasher = crc32fast::Hasher::new();
+            loop {
+                let n = reader.read(&mut buffer)
+                    .map_err(|e| format!("Read error: {}", e))?;
+                if n == 0 { break; }
+                hasher.update(&buffer[..n]);
+                bytes_read += n as u64;
+                state.progress.store(bytes_read, Ordering::SeqCst);
+            }
+            format!("{:08x}", hasher.finalize())
+        },
         "sha256" => {
             let mut hasher = Sha256::new();
             loop {
@@ -269,6 +353,45 @@ async fn calculate_hash(
     })
 }
 
+/// Compute every algorithm in `algorithms` from a single pass over the file
+/// at `path`, instead of one full read per algorithm like [`calculate_hash`].
+#[tauri::command]
+async fn calculate_hashes(
+    path: String,
+    algorithms: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<HashResult>, String> {
+    let path_buf = PathBuf::from(&path);
+
+    if !path_buf.exists() {
+        return Err("File not found".to_string());
+    }
+
+    let parsed: Vec<DigestAlgorithm> = algorithms
+        .iter()
+        .map(|a| DigestAlgorithm::parse(a))
+        .collect::<Result<_, _>>()?;
+
+    let (source, file_size) = split_image::open_image(path_buf.to_string_lossy().as_ref())?;
+    state.total_bytes.store(file_size, Ordering::SeqCst);
+    state.progress.store(0, Ordering::SeqCst);
+
+    let reader = BufReader::with_capacity(4 * 1024 * 1024, source);
+    let counting = compression::CountingReader::new(reader, state.progress.clone());
+
+    let results = verification::hash_reader_multi(counting, &parsed)?;
+
+    Ok(results
+        .into_iter()
+        .map(|(algorithm, hash)| HashResult {
+            algorithm: algorithm.name().to_string(),
+            hash,
+            verified: true,
+            expected: None,
+        })
+        .collect())
+}
+
 #[tauri::command]
 async fn verify_hash(
     path: String,
@@ -288,6 +411,35 @@ async fn verify_hash(
     })
 }
 
+/// Load a serialized [`hash_cascade::HashCascade`] from `path` into session
+/// state, so subsequent `classify_image` calls and the `burn_image`
+/// pre-flight gate (when `hash_cascade_gate` is set) can use it.
+#[tauri::command]
+async fn load_hash_cascade(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    let cascade = hash_cascade::load_hash_cascade(std::path::Path::new(&path))?;
+    let mut slot = state.hash_cascade.lock().await;
+    *slot = Some(cascade);
+    Ok(())
+}
+
+/// Classify an image's SHA-256 against the currently loaded hash cascade.
+/// Returns `Unknown` if no cascade has been loaded via `load_hash_cascade`.
+#[tauri::command]
+async fn classify_image(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<hash_cascade::Classification, String> {
+    let cascade = state.hash_cascade.lock().await;
+    let cascade = match cascade.as_ref() {
+        Some(cascade) => cascade,
+        None => return Ok(hash_cascade::Classification::Unknown),
+    };
+
+    let (source, _size) = split_image::open_image(&path)?;
+    let results = verification::hash_reader_multi(source, &[DigestAlgorithm::Sha256])?;
+    Ok(cascade.classify(&results[0].1))
+}
+
 // ============================================================================
 // Tauri Commands - Burning Operations
 // ============================================================================
@@ -305,7 +457,9 @@ async fn burn_image(
     state.is_burning.store(true, Ordering::SeqCst);
     state.cancel_flag.store(false, Ordering::SeqCst);
     state.progress.store(0, Ordering::SeqCst);
-    
+    state.blocks_written.store(0, Ordering::SeqCst);
+    state.blocks_skipped.store(0, Ordering::SeqCst);
+
     let start_time = std::time::Instant::now();
     
     // Update operation status
@@ -321,15 +475,82 @@ async fn burn_image(
         return Err("Image file not found".to_string());
     }
     
-    let image_size = std::fs::metadata(&image_path)
-        .map_err(|e| {
-            state.is_burning.store(false, Ordering::SeqCst);
-            format!("Failed to read image: {}", e)
-        })?
-        .len();
-    
+    let split_parts = detect_split_set(&image_path);
+    let image_size = match &split_parts {
+        Some(parts) => total_size(parts),
+        None => std::fs::metadata(&image_path)
+            .map_err(|e| {
+                state.is_burning.store(false, Ordering::SeqCst);
+                format!("Failed to read image: {}", e)
+            })?
+            .len(),
+    };
+
     state.total_bytes.store(image_size, Ordering::SeqCst);
-    
+
+    // A split image's sidecar checksum and signature manifest are published
+    // under the logical image's name, not its first part's - an
+    // `image.img.001` first part's sidecar is `image.img.sha256`, never
+    // `image.img.001.sha256`.
+    let logical_image_path = match &split_parts {
+        Some(_) => underlying_path(&image_path).to_string_lossy().to_string(),
+        None => config.image_path.clone(),
+    };
+
+    if config.require_signed_image {
+        match signing::check_trust(&config.image_path, &logical_image_path) {
+            signing::TrustStatus::Signed => {}
+            status => {
+                state.is_burning.store(false, Ordering::SeqCst);
+                return Err(format!(
+                    "Refusing to burn: image trust status is {:?}, but signing is required",
+                    status
+                ));
+            }
+        }
+    }
+
+    if config.hash_cascade_gate {
+        let cascade = state.hash_cascade.lock().await;
+        if let Some(cascade) = cascade.as_ref() {
+            let results = split_image::open_image(&config.image_path)
+                .and_then(|(source, _size)| verification::hash_reader_multi(source, &[DigestAlgorithm::Sha256]))
+                .map_err(|e| {
+                    state.is_burning.store(false, Ordering::SeqCst);
+                    e
+                })?;
+            let image_hash = &results[0].1;
+
+            if cascade.classify(image_hash) == hash_cascade::Classification::Excluded {
+                state.is_burning.store(false, Ordering::SeqCst);
+                return Err("Refusing to burn: image hash is on the revoked/excluded list".to_string());
+            }
+        }
+    }
+
+    // Step 0: Verify source image against a sidecar checksum, if one is present
+    if let Some(sidecar) = find_sidecar_hash(&logical_image_path) {
+        {
+            let mut op = state.current_operation.lock().await;
+            *op = format!("Verifying source against .{} sidecar...", sidecar.algorithm);
+        }
+
+        let result = verify_hash(
+            config.image_path.clone(),
+            sidecar.expected.clone(),
+            sidecar.algorithm.clone(),
+            state.clone(),
+        ).await?;
+
+        if !result.verified {
+            state.is_burning.store(false, Ordering::SeqCst);
+            return Err(format!(
+                "Source image failed sidecar {} verification: expected {}, got {}",
+                sidecar.algorithm, sidecar.expected, result.hash
+            ));
+        }
+    }
+
     // Step 1: Secure erase if requested
     if config.secure_erase_before {
         {
@@ -342,10 +563,12 @@ async fn burn_image(
             "random" => SecureEraseMethod::Random,
             "dod" => SecureEraseMethod::DoD,
             "gutmann" => SecureEraseMethod::Gutmann,
+            "hardware_sanitize" => SecureEraseMethod::HardwareSanitize,
+            "shred" => SecureEraseMethod::Shred { passes: 3, verify: true, final_zero: true },
             _ => SecureEraseMethod::Zeros,
         };
-        
-        secure_erase_drive(&config.target_drive, erase_method).await?;
+
+        secure_erase_drive(&config.target_drive, erase_method, config.force_erase).await?;
     }
     
     // Check for cancellation
@@ -360,26 +583,45 @@ async fn burn_image(
         *op = "Writing image to drive...".to_string();
     }
     
-    let bytes_written = write_image_to_drive(
-        &config.image_path,
-        &config.target_drive,
-        &state,
-    ).await?;
-    
+    // Delta mode re-reads the target block-by-block instead of blindly
+    // overwriting it, which needs random access to plaintext on both sides -
+    // incompatible with the AES-256-GCM inline seal above, and pointless if
+    // the target is shorter than the image (nothing to compare the tail
+    // against), so both cases fall back to a full write.
+    let delta_capable = config.delta_write
+        && config.encryption.as_ref().map_or(true, |e| !(e.enabled && e.encryption_type == "aes256"))
+        && secure_erase::get_device_size(&config.target_drive).map(|size| size >= image_size).unwrap_or(false);
+
+    let (bytes_written, blocks_written, blocks_skipped) = if delta_capable {
+        let outcome = write_image_to_drive_delta(&config.image_path, &config.target_drive, &state).await?;
+        (outcome.bytes_written, outcome.blocks_written, outcome.blocks_skipped)
+    } else {
+        let bytes_written = write_image_to_drive(
+            &config.image_path,
+            &config.target_drive,
+            config.encryption.as_ref(),
+            &state,
+        ).await?;
+        (bytes_written, 0, 0)
+    };
+
     // Check for cancellation
     if state.cancel_flag.load(Ordering::SeqCst) {
         state.is_burning.store(false, Ordering::SeqCst);
         return Err("Operation cancelled".to_string());
     }
-    
-    // Step 3: Setup encryption if requested
+
+    // Step 3: Setup encryption if requested. AES-256-GCM is sealed inline as
+    // the image streams past in `write_image_to_drive` above, since there's
+    // no external tool to hand the encrypted payload off to afterwards like
+    // there is for LUKS/VeraCrypt.
     if let Some(ref enc_settings) = config.encryption {
-        if enc_settings.enabled {
+        if enc_settings.enabled && enc_settings.encryption_type != "aes256" {
             {
                 let mut op = state.current_operation.lock().await;
                 *op = "Setting up encryption...".to_string();
             }
-            
+
             setup_encryption(&config.target_drive, enc_settings).await?;
         }
     }
@@ -402,7 +644,9 @@ async fn burn_image(
         Some(verify_written_image(
             &config.image_path,
             &config.target_drive,
-            image_size,
+            bytes_written,
+            &config.verify_algorithm,
+            config.encryption.as_ref(),
             &state,
         ).await?)
     } else {
@@ -435,52 +679,261 @@ async fn burn_image(
         hash_verification,
         duration_seconds: duration,
         bytes_written,
+        blocks_written,
+        blocks_skipped,
     })
 }
 
+/// Either side of the encryption fork in [`write_image_to_drive`]'s output
+/// path: a plain buffered file, or one sealing every chunk under
+/// AES-256-GCM as it passes through.
+enum TargetWriter {
+    Plain(BufWriter<File>),
+    Sealed(SealingWriter<BufWriter<File>>),
+}
+
+impl TargetWriter {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        match self {
+            TargetWriter::Plain(w) => w.write_all(buf),
+            TargetWriter::Sealed(w) => w.write_all(buf),
+        }
+    }
+
+    fn finish(self) -> std::io::Result<()> {
+        match self {
+            TargetWriter::Plain(mut w) => w.flush(),
+            TargetWriter::Sealed(w) => w.finish().map(|_| ()),
+        }
+    }
+}
+
 async fn write_image_to_drive(
     image_path: &str,
     target_drive: &str,
+    encryption: Option<&EncryptionSettings>,
     state: &State<'_, AppState>,
 ) -> Result<u64, String> {
-    let image_file = File::open(image_path)
-        .map_err(|e| format!("Failed to open image: {}", e))?;
-    
-    let target_file = std::fs::OpenOptions::new()
+    let (image_source, _image_size) = split_image::open_image(image_path)?;
+    let is_split_image = detect_split_set(Path::new(image_path)).is_some();
+
+    let mut target_file = std::fs::OpenOptions::new()
         .write(true)
         .open(target_drive)
         .map_err(|e| format!("Failed to open target drive: {}", e))?;
-    
-    let mut reader = BufReader::with_capacity(4 * 1024 * 1024, image_file);
-    let mut writer = BufWriter::with_capacity(4 * 1024 * 1024, target_file);
-    
+
+    let mut target_writer = match encryption {
+        Some(settings) if settings.enabled && settings.encryption_type == "aes256" => {
+            let header = StreamHeader::generate();
+            target_file
+                .write_all(&header.to_bytes())
+                .map_err(|e| format!("Failed to write AES-GCM stream header: {}", e))?;
+            let key = encrypted_stream::derive_stream_key(&settings.password, &header)?;
+            let sealing = SealingWriter::new(
+                BufWriter::with_capacity(4 * 1024 * 1024, target_file),
+                &key,
+                header.nonce_prefix,
+            )?;
+            TargetWriter::Sealed(sealing)
+        }
+        _ => TargetWriter::Plain(BufWriter::with_capacity(4 * 1024 * 1024, target_file)),
+    };
+
+    let raw_reader = BufReader::with_capacity(4 * 1024 * 1024, image_source);
+    let (format, sniffed) = sniff_and_rewind(raw_reader)
+        .map_err(|e| format!("Failed to sniff image format: {}", e))?;
+    let counting = compression::CountingReader::new(sniffed, state.compressed_bytes_read.clone());
+    let mut reader = decoder_for(counting, format)?;
+
+    // The decompressed size isn't known up front for every container, so
+    // `total_bytes` tracks whichever quantity progress is actually measured
+    // against: the true uncompressed size when the container records one,
+    // otherwise the compressed byte count already in `total_bytes` from the
+    // caller, with progress reported against bytes consumed instead of
+    // bytes written. The hint reads size metadata straight off the one file
+    // at `image_path` (for gzip and xz, a trailing footer), so it only means
+    // anything for a single file - a split image always falls back to
+    // compressed-bytes progress instead.
+    let track_compressed_progress = if format != CompressionFormat::None {
+        let mut op = state.current_operation.lock().await;
+        *op = format!("Decompressing {} image and writing to drive...", format.name());
+
+        let size_hint = if is_split_image {
+            None
+        } else {
+            uncompressed_size_hint(Path::new(image_path), format)
+        };
+
+        match size_hint {
+            Some(size) => {
+                state.total_bytes.store(size, Ordering::SeqCst);
+                false
+            }
+            None => true,
+        }
+    } else {
+        false
+    };
+
     let mut buffer = vec![0u8; 4 * 1024 * 1024]; // 4MB buffer
     let mut bytes_written = 0u64;
-    
+
     loop {
         // Check for cancellation
         if state.cancel_flag.load(Ordering::SeqCst) {
             return Err("Operation cancelled".to_string());
         }
-        
+
         let n = reader.read(&mut buffer)
             .map_err(|e| format!("Read error: {}", e))?;
-        
+
         if n == 0 { break; }
-        
-        writer.write_all(&buffer[..n])
+
+        target_writer.write_all(&buffer[..n])
             .map_err(|e| format!("Write error: {}", e))?;
-        
+
         bytes_written += n as u64;
-        state.progress.store(bytes_written, Ordering::SeqCst);
+        if track_compressed_progress {
+            state.progress.store(state.compressed_bytes_read.load(Ordering::SeqCst), Ordering::SeqCst);
+        } else {
+            state.progress.store(bytes_written, Ordering::SeqCst);
+        }
     }
-    
-    writer.flush()
+
+    target_writer.finish()
         .map_err(|e| format!("Flush error: {}", e))?;
-    
+
     Ok(bytes_written)
 }
 
+/// Fixed block size for [`write_image_to_drive_delta`], in the 1-4 MiB range
+/// proxmox-backup uses for its own chunk digests - large enough that CRC32
+/// over a block is cheap relative to the write it might save, small enough
+/// that a single changed byte doesn't force rewriting a huge span.
+const DELTA_BLOCK_SIZE: usize = 2 * 1024 * 1024;
+
+/// Outcome of a [`write_image_to_drive_delta`] pass.
+struct DeltaWriteOutcome {
+    bytes_written: u64,
+    blocks_written: u64,
+    blocks_skipped: u64,
+}
+
+/// Fill `buf` from `reader`, short-circuiting at EOF. Like
+/// [`Read::read_exact`] but tolerates a final partial block instead of
+/// erroring, returning how many bytes actually landed in `buf`.
+fn read_full_block<R: Read>(reader: &mut R, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut total = 0;
+    while total < buf.len() {
+        match reader.read(&mut buf[total..])? {
+            0 => break,
+            n => total += n,
+        }
+    }
+    Ok(total)
+}
+
+/// Incremental write pass: read the image one [`DELTA_BLOCK_SIZE`] block at
+/// a time, compare a CRC32 of it against the same-offset block already on
+/// `target_drive`, and only write the block back when they differ. Skips
+/// the AES-256-GCM/compressed-progress machinery in [`write_image_to_drive`]
+/// since delta mode only ever runs against a plain target with a target at
+/// least as large as the image - both checked by the caller before this is
+/// called.
+async fn write_image_to_drive_delta(
+    image_path: &str,
+    target_drive: &str,
+    state: &State<'_, AppState>,
+) -> Result<DeltaWriteOutcome, String> {
+    let (image_source, _image_size) = split_image::open_image(image_path)?;
+    let is_split_image = detect_split_set(Path::new(image_path)).is_some();
+
+    let mut target_file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(target_drive)
+        .map_err(|e| format!("Failed to open target drive: {}", e))?;
+
+    let raw_reader = BufReader::with_capacity(4 * 1024 * 1024, image_source);
+    let (format, sniffed) = sniff_and_rewind(raw_reader)
+        .map_err(|e| format!("Failed to sniff image format: {}", e))?;
+    let counting = compression::CountingReader::new(sniffed, state.compressed_bytes_read.clone());
+    let mut reader = decoder_for(counting, format)?;
+
+    let track_compressed_progress = if format != CompressionFormat::None {
+        let mut op = state.current_operation.lock().await;
+        *op = format!("Decompressing {} image and comparing blocks...", format.name());
+
+        // As in `write_image_to_drive`: the hint reads size metadata straight
+        // off one file on disk, so it's only meaningful for a single file.
+        let size_hint = if is_split_image {
+            None
+        } else {
+            uncompressed_size_hint(Path::new(image_path), format)
+        };
+
+        match size_hint {
+            Some(size) => {
+                state.total_bytes.store(size, Ordering::SeqCst);
+                false
+            }
+            None => true,
+        }
+    } else {
+        let mut op = state.current_operation.lock().await;
+        *op = "Comparing blocks and writing changes...".to_string();
+        false
+    };
+
+    let mut image_block = vec![0u8; DELTA_BLOCK_SIZE];
+    let mut target_block = vec![0u8; DELTA_BLOCK_SIZE];
+    let mut offset = 0u64;
+    let mut bytes_written = 0u64;
+    let mut blocks_written = 0u64;
+    let mut blocks_skipped = 0u64;
+
+    loop {
+        if state.cancel_flag.load(Ordering::SeqCst) {
+            return Err("Operation cancelled".to_string());
+        }
+
+        let n = read_full_block(&mut reader, &mut image_block)
+            .map_err(|e| format!("Read error: {}", e))?;
+        if n == 0 { break; }
+        let image_slice = &image_block[..n];
+
+        target_file.seek(SeekFrom::Start(offset))
+            .map_err(|e| format!("Failed to seek target drive: {}", e))?;
+        let target_read = read_full_block(&mut target_file, &mut target_block[..n]).unwrap_or(0);
+
+        let unchanged = target_read == n
+            && crc32fast::hash(&target_block[..n]) == crc32fast::hash(image_slice);
+
+        if unchanged {
+            blocks_skipped += 1;
+        } else {
+            target_file.seek(SeekFrom::Start(offset))
+                .map_err(|e| format!("Failed to seek target drive: {}", e))?;
+            target_file.write_all(image_slice)
+                .map_err(|e| format!("Write error: {}", e))?;
+            blocks_written += 1;
+        }
+
+        offset += n as u64;
+        bytes_written += n as u64;
+        state.blocks_written.store(blocks_written, Ordering::SeqCst);
+        state.blocks_skipped.store(blocks_skipped, Ordering::SeqCst);
+
+        if track_compressed_progress {
+            state.progress.store(state.compressed_bytes_read.load(Ordering::SeqCst), Ordering::SeqCst);
+        } else {
+            state.progress.store(bytes_written, Ordering::SeqCst);
+        }
+    }
+
+    Ok(DeltaWriteOutcome { bytes_written, blocks_written, blocks_skipped })
+}
+
 async fn setup_encryption(
     target_drive: &str,
     settings: &EncryptionSettings,
@@ -553,12 +1006,43 @@ async fn setup_luks_encryption(
 }
 
 async fn setup_veracrypt_encryption(
-    _target_drive: &str,
-    _settings: &EncryptionSettings,
+    target_drive: &str,
+    settings: &EncryptionSettings,
 ) -> Result<(), String> {
-    // VeraCrypt CLI support would go here
-    // For now, return instructions
-    Err("VeraCrypt encryption requires veracrypt CLI. Please install veracrypt first.".to_string())
+    let hash_algo = match settings.hash_algo.to_lowercase().as_str() {
+        "sha256" => veracrypt_hashes::SHA256,
+        "whirlpool" => veracrypt_hashes::WHIRLPOOL,
+        _ => veracrypt_hashes::SHA512,
+    };
+
+    let target = std::fs::OpenOptions::new()
+        .write(true)
+        .open(target_drive)
+        .map_err(|e| format!("Failed to open target drive: {}", e))?;
+
+    // `target.metadata().len()` reports 0 for a block special file (its
+    // st_size isn't the device capacity), so the volume size has to come
+    // from the same ioctl-based sizing secure erase uses.
+    let volume_size = secure_erase::get_device_size(target_drive)?;
+
+    let meta = VeraCryptHeader::new(volume_size, encryption::veracrypt_algorithms::AES, hash_algo);
+    let header = veracrypt::create_header(&settings.password, &meta);
+
+    let mut writer = BufWriter::new(target);
+    writer.write_all(&header.bytes)
+        .map_err(|e| format!("Failed to write VeraCrypt header: {}", e))?;
+
+    // Backup header copy goes at the end of the volume, as VeraCrypt does.
+    writer.seek(std::io::SeekFrom::End(-(veracrypt::TOTAL_HEADER_SIZE as i64)))
+        .map_err(|e| format!("Failed to seek to backup header: {}", e))?;
+
+    let backup_header = veracrypt::create_backup_header(&settings.password, &meta, &header.master_key_area);
+    writer.write_all(&backup_header.bytes)
+        .map_err(|e| format!("Failed to write VeraCrypt backup header: {}", e))?;
+
+    writer.flush().map_err(|e| format!("Flush error: {}", e))?;
+
+    Ok(())
 }
 
 async fn configure_bootloader(
@@ -584,53 +1068,76 @@ async fn configure_bootloader(
     }
 }
 
+/// Hash the source image - decompressing it first if it's a compressed
+/// container, since that's what actually landed on the drive - and read
+/// back exactly `written_bytes` bytes from the target device, in parallel,
+/// on two dedicated threads, so verifying a burn doesn't re-read the image
+/// a second time on top of the pass that already streamed it onto the drive.
+/// When `encryption` names the AES-256-GCM stream mode, the target side is
+/// opened back up through [`OpeningReader`] first, so the hash being
+/// compared is always of plaintext on both sides.
 async fn verify_written_image(
     image_path: &str,
     target_drive: &str,
-    image_size: u64,
+    written_bytes: u64,
+    algorithm: &str,
+    encryption: Option<&EncryptionSettings>,
     state: &State<'_, AppState>,
 ) -> Result<HashResult, String> {
-    // Calculate hash of original image
-    let image_file = File::open(image_path)
-        .map_err(|e| format!("Failed to open image: {}", e))?;
-    
-    let mut reader = BufReader::with_capacity(4 * 1024 * 1024, image_file);
-    let mut hasher = Sha256::new();
-    let mut buffer = vec![0u8; 4 * 1024 * 1024];
-    
-    loop {
-        let n = reader.read(&mut buffer)
-            .map_err(|e| format!("Read error: {}", e))?;
-        if n == 0 { break; }
-        hasher.update(&buffer[..n]);
-    }
-    
-    let original_hash = hex::encode(hasher.finalize());
-    
-    // Calculate hash of written data
-    let target_file = File::open(target_drive)
-        .map_err(|e| format!("Failed to open target: {}", e))?;
-    
-    let mut reader = BufReader::with_capacity(4 * 1024 * 1024, target_file);
-    let mut hasher = Sha256::new();
-    let mut bytes_read = 0u64;
-    
+    let algorithm = if algorithm.is_empty() { "sha256" } else { algorithm };
+    let digest_algorithm = DigestAlgorithm::parse(algorithm)?;
+
     state.progress.store(0, Ordering::SeqCst);
-    
-    while bytes_read < image_size {
-        let to_read = std::cmp::min(buffer.len() as u64, image_size - bytes_read) as usize;
-        let n = reader.read(&mut buffer[..to_read])
-            .map_err(|e| format!("Read error: {}", e))?;
-        if n == 0 { break; }
-        hasher.update(&buffer[..n]);
-        bytes_read += n as u64;
-        state.progress.store(bytes_read, Ordering::SeqCst);
-    }
-    
-    let written_hash = hex::encode(hasher.finalize());
-    
+
+    let image_path = image_path.to_string();
+    let target_drive = target_drive.to_string();
+    let target_progress = state.progress.clone();
+    let encryption = encryption.cloned();
+
+    let image_handle = std::thread::spawn(move || {
+        let (image_source, _image_size) = split_image::open_image(&image_path)?;
+        let raw_reader = BufReader::with_capacity(4 * 1024 * 1024, image_source);
+        let (format, sniffed) = sniff_and_rewind(raw_reader)
+            .map_err(|e| format!("Failed to sniff image format: {}", e))?;
+        let decoded = decoder_for(sniffed, format)?;
+        verification::hash_reader_multi(decoded, &[digest_algorithm])
+    });
+
+    let target_handle = std::thread::spawn(move || {
+        let mut target_file = File::open(&target_drive)
+            .map_err(|e| format!("Failed to open target: {}", e))?;
+
+        match encryption {
+            Some(settings) if settings.enabled && settings.encryption_type == "aes256" => {
+                let mut header_bytes = [0u8; AES_GCM_HEADER_SIZE];
+                target_file.read_exact(&mut header_bytes)
+                    .map_err(|e| format!("Failed to read AES-GCM stream header: {}", e))?;
+                let header = StreamHeader::from_bytes(&header_bytes)?;
+                let key = encrypted_stream::derive_stream_key(&settings.password, &header)?;
+
+                let reader = BufReader::with_capacity(4 * 1024 * 1024, target_file);
+                let opened = OpeningReader::new(reader, &key, header.nonce_prefix)?.take(written_bytes);
+                let counting = compression::CountingReader::new(opened, target_progress);
+                verification::hash_reader_multi(counting, &[digest_algorithm])
+            }
+            _ => {
+                let reader = BufReader::with_capacity(4 * 1024 * 1024, target_file).take(written_bytes);
+                let counting = compression::CountingReader::new(reader, target_progress);
+                verification::hash_reader_multi(counting, &[digest_algorithm])
+            }
+        }
+    });
+
+    let image_results = image_handle.join()
+        .map_err(|_| "Image hashing thread panicked".to_string())??;
+    let target_results = target_handle.join()
+        .map_err(|_| "Target hashing thread panicked".to_string())??;
+
+    let original_hash = image_results[0].1.clone();
+    let written_hash = target_results[0].1.clone();
+
     Ok(HashResult {
-        algorithm: "sha256".to_string(),
+        algorithm: algorithm.to_string(),
         hash: written_hash.clone(),
         verified: original_hash == written_hash,
         expected: Some(original_hash),
@@ -655,14 +1162,19 @@ async fn get_burn_progress(state: State<'_, AppState>) -> Result<BurnProgress, S
         0.0
     };
     
+    let compressed_read = state.compressed_bytes_read.load(Ordering::SeqCst);
+
     Ok(BurnProgress {
         stage: operation,
         progress_percent: percent,
         bytes_written: progress,
         total_bytes: total,
+        compressed_bytes_read: compressed_read,
         speed_mbps: 0.0, // Would need timing to calculate
         eta_seconds: 0,   // Would need timing to calculate
         message: String::new(),
+        blocks_written: state.blocks_written.load(Ordering::SeqCst),
+        blocks_skipped: state.blocks_skipped.load(Ordering::SeqCst),
     })
 }
 
@@ -674,29 +1186,163 @@ async fn get_burn_progress(state: State<'_, AppState>) -> Result<BurnProgress, S
 async fn secure_erase(
     device: String,
     method: String,
+    force: bool,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<ErasureCertificate, String> {
     if state.is_burning.load(Ordering::SeqCst) {
         return Err("Another operation is in progress".to_string());
     }
-    
+
     state.is_burning.store(true, Ordering::SeqCst);
-    
+    state.cancel_flag.store(false, Ordering::SeqCst);
+    state.progress.store(0, Ordering::SeqCst);
+    state.total_bytes.store(0, Ordering::SeqCst);
+
     let erase_method = match method.as_str() {
         "zeros" => SecureEraseMethod::Zeros,
         "random" => SecureEraseMethod::Random,
         "dod" => SecureEraseMethod::DoD,
         "gutmann" => SecureEraseMethod::Gutmann,
+        "hardware_sanitize" => SecureEraseMethod::HardwareSanitize,
+        "shred" => SecureEraseMethod::Shred { passes: 3, verify: true, final_zero: true },
         _ => SecureEraseMethod::Zeros,
     };
-    
-    let result = secure_erase_drive(&device, erase_method).await;
-    
+
+    let (progress_tx, progress_rx) = std::sync::mpsc::channel();
+    let cancel = CancellationToken::from_shared(state.cancel_flag.clone());
+
+    // Drain progress snapshots on their own thread so the erase loop (which
+    // blocks this task on synchronous device I/O) never waits on a full
+    // channel, mirroring how `get_burn_progress` is polled off the same
+    // shared atomics for a burn in progress.
+    let progress_state = (state.progress.clone(), state.total_bytes.clone(), state.current_operation.clone());
+    let progress_thread = std::thread::spawn(move || {
+        let (progress, total_bytes, current_operation) = progress_state;
+        for snapshot in progress_rx {
+            total_bytes.store(snapshot.total_size.saturating_mul(snapshot.total_passes as u64), Ordering::SeqCst);
+            let done = snapshot.total_size.saturating_mul(snapshot.pass_index as u64) + snapshot.bytes_written_this_pass;
+            progress.store(done, Ordering::SeqCst);
+            *current_operation.blocking_lock() = format!(
+                "Erasing pass {}/{}...",
+                snapshot.pass_index + 1,
+                snapshot.total_passes
+            );
+        }
+    });
+
+    let result = secure_erase_drive_with_progress(&device, erase_method, force, progress_tx, cancel)
+        .await
+        .map_err(String::from);
+
+    progress_thread.join().ok();
     state.is_burning.store(false, Ordering::SeqCst);
-    
+
     result
 }
 
+/// Whether `device` is backed by spinning media rather than flash. The
+/// frontend uses this to steer users away from pass-based methods like
+/// Gutmann on SSDs/NVMe, where `hardware_sanitize` is faster and more
+/// thorough than an overwrite.
+#[tauri::command]
+async fn drive_is_rotational(device: String) -> Result<bool, String> {
+    Ok(secure_erase::is_rotational(&device))
+}
+
+// ============================================================================
+// Tauri Commands - Partition Table Management
+// ============================================================================
+
+#[tauri::command]
+async fn backup_partition_table(device: String, out_path: String) -> Result<(), String> {
+    partitioning::backup_to_file(&device, &out_path)
+}
+
+#[tauri::command]
+async fn restore_partition_table(device: String, backup_path: String) -> Result<(), String> {
+    let backup = partitioning::load_backup(&backup_path)?;
+    partitioning::restore_partition_table(&device, &backup)
+}
+
+// ============================================================================
+// Tauri Commands - Image Signing
+// ============================================================================
+
+#[tauri::command]
+async fn get_image_trust_status(path: String) -> Result<signing::TrustStatus, String> {
+    let path_buf = PathBuf::from(&path);
+    let logical_path = match detect_split_set(&path_buf) {
+        Some(_) => underlying_path(&path_buf).to_string_lossy().to_string(),
+        None => path.clone(),
+    };
+    Ok(signing::check_trust(&path, &logical_path))
+}
+
+// ============================================================================
+// Tauri Commands - Boot Measurement
+// ============================================================================
+
+/// Well-known paths of EFI executables that make up a boot chain, relative
+/// to the root of a mounted EFI System Partition, checked in the order a
+/// firmware's boot manager would reach them (fallback loader, then
+/// distro-specific shim/GRUB).
+const CANDIDATE_EFI_PATHS: &[&str] = &[
+    "EFI/BOOT/BOOTX64.EFI",
+    "EFI/BOOT/BOOTIA32.EFI",
+    "EFI/BOOT/BOOTAA64.EFI",
+    "EFI/Microsoft/Boot/bootmgfw.efi",
+    "EFI/debian/shimx64.efi",
+    "EFI/debian/grubx64.efi",
+    "EFI/ubuntu/shimx64.efi",
+    "EFI/ubuntu/grubx64.efi",
+    "EFI/fedora/shimx64.efi",
+    "EFI/fedora/grubx64.efi",
+];
+
+/// Predict the TPM PCR[4] value a UEFI firmware will measure for the boot
+/// chain written to `target_drive`. Runs after `configure_bootloader` has
+/// written the EFI System Partition: finds it among the drive's mounted
+/// partitions (the same way [`drives::list_drives`] reports any other
+/// partition, rather than parsing the FAT filesystem directly), hashes
+/// whichever well-known boot executables it finds with Authenticode, and
+/// folds those digests into PCR[4] in [`pe_measure::measure_boot_chain`].
+#[tauri::command]
+async fn compute_boot_measurements(
+    target_drive: String,
+    algo: String,
+) -> Result<pe_measure::BootMeasurement, String> {
+    let alg = pe_measure::PcrHashAlg::parse(&algo)?;
+
+    let drives = list_drives().await?;
+    let drive = drives
+        .into_iter()
+        .find(|d| d.device == target_drive)
+        .ok_or_else(|| format!("Drive {} not found", target_drive))?;
+
+    let esp_root = drive
+        .partitions
+        .iter()
+        .find(|p| p.filesystem.eq_ignore_ascii_case("vfat") && p.mount_point.is_some())
+        .and_then(|p| p.mount_point.clone())
+        .ok_or_else(|| "No mounted EFI System Partition found on this drive".to_string())?;
+
+    let mut components = Vec::new();
+    for relative_path in CANDIDATE_EFI_PATHS {
+        let full_path = PathBuf::from(&esp_root).join(relative_path);
+        if full_path.is_file() {
+            let bytes = std::fs::read(&full_path)
+                .map_err(|e| format!("Failed to read {}: {}", full_path.display(), e))?;
+            components.push((full_path.to_string_lossy().to_string(), bytes));
+        }
+    }
+
+    if components.is_empty() {
+        return Err("No known EFI boot executables found on the EFI System Partition".to_string());
+    }
+
+    pe_measure::measure_boot_chain(&components, alg)
+}
+
 // ============================================================================
 // Main Entry Point
 // ============================================================================
@@ -712,13 +1358,24 @@ fn main() {
             // Image operations
             get_image_info,
             calculate_hash,
+            calculate_hashes,
             verify_hash,
+            load_hash_cascade,
+            classify_image,
             // Burning operations
             burn_image,
             cancel_burn,
             get_burn_progress,
             // Secure erase
             secure_erase,
+            drive_is_rotational,
+            // Partition table management
+            backup_partition_table,
+            restore_partition_table,
+            // Image signing
+            get_image_trust_status,
+            // Boot measurement
+            compute_boot_measurements,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,302 @@
+//! GPT/MBR partition table backup, restore, and selective preservation
+//!
+//! Lets users snapshot a target drive's partition layout before a destructive
+//! burn, restore it afterwards, or preserve specific partitions by label or
+//! index across the write — the way `coreos-installer` saves partitions
+//! matching `--save-partlabel`/`--save-partindex`.
+
+use gptman::{GPT, GPTPartitionEntry};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+
+use crate::secure_erase;
+
+/// A partition selector matching `coreos-installer`'s `--save-partlabel` /
+/// `--save-partindex` flags.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PartitionSelector {
+    Label(String),
+    Index(u32),
+}
+
+/// A previously-read GPT, kept alongside the sector size it was read with
+/// so it can be replayed onto a (possibly different) device later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartitionTableBackup {
+    pub disk_guid: [u8; 16],
+    pub sector_size: u64,
+    pub partitions: Vec<SavedPartition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedPartition {
+    pub index: u32,
+    pub partition_type_guid: [u8; 16],
+    pub unique_partition_guid: [u8; 16],
+    pub starting_lba: u64,
+    pub ending_lba: u64,
+    pub attribute_bits: u64,
+    pub partition_name: String,
+}
+
+impl SavedPartition {
+    fn from_entry(index: u32, entry: &GPTPartitionEntry) -> Self {
+        Self {
+            index,
+            partition_type_guid: entry.partition_type_guid,
+            unique_partition_guid: entry.unique_partition_guid,
+            starting_lba: entry.starting_lba,
+            ending_lba: entry.ending_lba,
+            attribute_bits: entry.attribute_bits,
+            partition_name: entry.partition_name.as_str().to_string(),
+        }
+    }
+
+    fn matches(&self, selector: &PartitionSelector) -> bool {
+        match selector {
+            PartitionSelector::Label(label) => &self.partition_name == label,
+            PartitionSelector::Index(index) => &self.index == index,
+        }
+    }
+}
+
+/// Read the existing primary+backup GPT off `device` and return a
+/// serializable snapshot of its disk GUID and partition entries.
+pub fn read_partition_table(device: &str) -> Result<PartitionTableBackup, String> {
+    let mut file = File::open(device)
+        .map_err(|e| format!("Failed to open {}: {}", device, e))?;
+
+    let gpt = GPT::find_from(&mut file)
+        .map_err(|e| format!("Failed to read GPT from {}: {}", device, e))?;
+
+    let partitions = gpt
+        .iter()
+        .filter(|(_, p)| p.is_used())
+        .map(|(i, p)| SavedPartition::from_entry(i, p))
+        .collect();
+
+    Ok(PartitionTableBackup {
+        disk_guid: gpt.header.disk_guid,
+        sector_size: gpt.sector_size,
+        partitions,
+    })
+}
+
+/// Dump the partition table to a JSON file for later `restore_partition_table`.
+pub fn backup_to_file(device: &str, out_path: &str) -> Result<(), String> {
+    let backup = read_partition_table(device)?;
+    let json = serde_json::to_string_pretty(&backup)
+        .map_err(|e| format!("Failed to serialize partition table: {}", e))?;
+    std::fs::write(out_path, json)
+        .map_err(|e| format!("Failed to write backup file {}: {}", out_path, e))
+}
+
+/// Load a previously-saved partition table snapshot.
+pub fn load_backup(path: &str) -> Result<PartitionTableBackup, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read backup file {}: {}", path, e))?;
+    serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse backup file {}: {}", path, e))
+}
+
+/// Write a fresh GPT onto `device` containing the partitions from `backup`,
+/// restoring the disk GUID and every saved partition entry verbatim.
+pub fn restore_partition_table(device: &str, backup: &PartitionTableBackup) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(device)
+        .map_err(|e| format!("Failed to open {}: {}", device, e))?;
+
+    let mut gpt = GPT::new_from(&mut file, backup.sector_size, backup.disk_guid)
+        .map_err(|e| format!("Failed to initialize GPT on {}: {}", device, e))?;
+
+    for saved in &backup.partitions {
+        gpt[saved.index] = GPTPartitionEntry {
+            partition_type_guid: saved.partition_type_guid,
+            unique_partition_guid: saved.unique_partition_guid,
+            starting_lba: saved.starting_lba,
+            ending_lba: saved.ending_lba,
+            attribute_bits: saved.attribute_bits,
+            partition_name: saved.partition_name.as_str().into(),
+        };
+    }
+
+    gpt.write_into(&mut file)
+        .map_err(|e| format!("Failed to write GPT to {}: {}", device, e))?;
+
+    reread_partition_table(&file);
+
+    Ok(())
+}
+
+/// Re-create the partitions in `selectors` (matched against the pre-burn
+/// `backup`) in the free space following the flashed payload, starting right
+/// after `payload_end_lba`.
+pub fn restore_selected_partitions(
+    device: &str,
+    backup: &PartitionTableBackup,
+    selectors: &[PartitionSelector],
+    payload_end_lba: u64,
+) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(device)
+        .map_err(|e| format!("Failed to open {}: {}", device, e))?;
+
+    let mut gpt = GPT::find_from(&mut file)
+        .or_else(|_| GPT::new_from(&mut file, backup.sector_size, backup.disk_guid))
+        .map_err(|e| format!("Failed to read/initialize GPT on {}: {}", device, e))?;
+
+    let mut next_lba = payload_end_lba + 1;
+
+    for saved in &backup.partitions {
+        if !selectors.iter().any(|s| saved.matches(s)) {
+            continue;
+        }
+
+        let size_lba = saved.ending_lba.saturating_sub(saved.starting_lba);
+        let starting_lba = next_lba;
+        let ending_lba = starting_lba + size_lba;
+
+        gpt[saved.index] = GPTPartitionEntry {
+            partition_type_guid: saved.partition_type_guid,
+            unique_partition_guid: saved.unique_partition_guid,
+            starting_lba,
+            ending_lba,
+            attribute_bits: saved.attribute_bits,
+            partition_name: saved.partition_name.as_str().into(),
+        };
+
+        next_lba = ending_lba + 1;
+    }
+
+    gpt.write_into(&mut file)
+        .map_err(|e| format!("Failed to write GPT to {}: {}", device, e))?;
+
+    reread_partition_table(&file);
+
+    Ok(())
+}
+
+/// Write a fresh GPT/MBR onto `device` with the user-provided partitions,
+/// replacing whatever table (if any) was there before.
+pub fn write_fresh_table(device: &str, disk_guid: [u8; 16], sector_size: u64, partitions: &[SavedPartition]) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(device)
+        .map_err(|e| format!("Failed to open {}: {}", device, e))?;
+
+    let mut gpt = GPT::new_from(&mut file, sector_size, disk_guid)
+        .map_err(|e| format!("Failed to initialize GPT on {}: {}", device, e))?;
+
+    for p in partitions {
+        gpt[p.index] = GPTPartitionEntry {
+            partition_type_guid: p.partition_type_guid,
+            unique_partition_guid: p.unique_partition_guid,
+            starting_lba: p.starting_lba,
+            ending_lba: p.ending_lba,
+            attribute_bits: p.attribute_bits,
+            partition_name: p.partition_name.as_str().into(),
+        };
+    }
+
+    gpt.write_into(&mut file)
+        .map_err(|e| format!("Failed to write GPT to {}: {}", device, e))?;
+
+    reread_partition_table(&file);
+
+    Ok(())
+}
+
+/// Zero the GPT/MBR structures on `device` natively, without shelling out to
+/// `wipefs`/`dd`: the protective MBR at LBA 0 and, when a valid GPT can be
+/// read, the primary header at LBA 1 and the backup header at its recorded
+/// LBA. Falls back to zeroing the leading and trailing 1MiB - sized from the
+/// ioctl-derived device size, not `size / 1MB - 1` - when no valid GPT is
+/// present. Finishes with a `BLKRRPART` re-read so the kernel drops any
+/// stale partition nodes.
+pub fn wipe_partition_tables(device: &str) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(device)
+        .map_err(|e| format!("Failed to open {}: {}", device, e))?;
+
+    match GPT::find_from(&mut file) {
+        Ok(gpt) => {
+            let sector_size = gpt.sector_size;
+            let backup_lba = gpt.header.backup_lba;
+            zero_sectors(&mut file, 0, 1, sector_size)?;
+            zero_sectors(&mut file, 1, 1, sector_size)?;
+            zero_sectors(&mut file, backup_lba, 1, sector_size)?;
+        }
+        Err(_) => {
+            const REGION: u64 = 1024 * 1024;
+            let size = secure_erase::get_device_size(device)?;
+            zero_region(&mut file, 0, REGION.min(size))?;
+            let tail_start = size.saturating_sub(REGION);
+            zero_region(&mut file, tail_start, size - tail_start)?;
+        }
+    }
+
+    file.sync_all().map_err(|e| format!("Failed to sync {}: {}", device, e))?;
+    reread_partition_table(&file);
+
+    Ok(())
+}
+
+/// Overwrite `count` sectors of `sector_size` bytes starting at `lba`.
+fn zero_sectors(file: &mut File, lba: u64, count: u64, sector_size: u64) -> Result<(), String> {
+    zero_region(file, lba * sector_size, count * sector_size)
+}
+
+/// Overwrite `len` bytes starting at byte `offset`.
+fn zero_region(file: &mut File, offset: u64, len: u64) -> Result<(), String> {
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("Failed to seek to offset {}: {}", offset, e))?;
+    file.write_all(&vec![0u8; len as usize])
+        .map_err(|e| format!("Failed to zero {} bytes at offset {}: {}", len, offset, e))
+}
+
+/// Ask the kernel to re-read the partition table so the new layout shows up
+/// without requiring a replug (Linux only; a no-op elsewhere).
+#[cfg(target_os = "linux")]
+fn reread_partition_table(file: &File) {
+    use std::os::unix::io::AsRawFd;
+
+    const BLKRRPART: libc::c_ulong = 0x125f;
+
+    unsafe {
+        libc::ioctl(file.as_raw_fd(), BLKRRPART);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reread_partition_table(_file: &File) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selector_matches_by_label_and_index() {
+        let saved = SavedPartition {
+            index: 1,
+            partition_type_guid: [0; 16],
+            unique_partition_guid: [0; 16],
+            starting_lba: 2048,
+            ending_lba: 4096,
+            attribute_bits: 0,
+            partition_name: "ESP".to_string(),
+        };
+
+        assert!(saved.matches(&PartitionSelector::Label("ESP".to_string())));
+        assert!(saved.matches(&PartitionSelector::Index(1)));
+        assert!(!saved.matches(&PartitionSelector::Label("DATA".to_string())));
+        assert!(!saved.matches(&PartitionSelector::Index(2)));
+    }
+}
@@ -0,0 +1,454 @@
+//! Authenticode PE hashing and TPM PCR[4] boot-chain measurement
+//!
+//! `BootloaderConfig::secure_boot` tells the burner to set up Secure Boot,
+//! but nothing in this crate predicts what the firmware will actually
+//! measure into the TPM for it. This module computes that prediction so the
+//! caller can pre-seal a PCR policy or detect that the written boot chain
+//! doesn't match what was expected.
+//!
+//! Two independent pieces:
+//!
+//! - [`authenticode_digest`] hashes a PE/COFF file the way the Authenticode
+//!   spec does: the headers rounded up to `SizeOfHeaders`, then every
+//!   section's raw data in `PointerToRawData` order (not file-declaration
+//!   order, since a linker is free to lay sections out non-contiguously),
+//!   then any trailing data up to (but not including) the attribute
+//!   certificate table - skipping only the `CheckSum` field, the
+//!   Certificate Table directory entry, and the certificate table itself,
+//!   since none of those affect what the image actually executes.
+//! - [`extend_pcr`] replays the TPM's `PCR_Extend` operation
+//!   (`PCR_new = H(PCR_old || measurement)`) so a whole boot chain's final
+//!   PCR[4] value can be folded up from an all-zero start without a TPM
+//!   present.
+
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+
+/// Hash algorithm used both for each component's Authenticode digest and for
+/// the PCR extend operations that fold them together. Real PCR banks are
+/// fixed per-TPM (SHA-1 on older firmware, SHA-256 on current ones), so the
+/// caller selects one algorithm and uses it throughout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcrHashAlg {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl PcrHashAlg {
+    pub fn parse(algorithm: &str) -> Result<Self, String> {
+        match algorithm.to_lowercase().as_str() {
+            "sha1" => Ok(PcrHashAlg::Sha1),
+            "sha256" => Ok(PcrHashAlg::Sha256),
+            "sha512" => Ok(PcrHashAlg::Sha512),
+            other => Err(format!("Unsupported PCR hash algorithm: {}", other)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PcrHashAlg::Sha1 => "sha1",
+            PcrHashAlg::Sha256 => "sha256",
+            PcrHashAlg::Sha512 => "sha512",
+        }
+    }
+
+    fn digest_size(&self) -> usize {
+        match self {
+            PcrHashAlg::Sha1 => 20,
+            PcrHashAlg::Sha256 => 32,
+            PcrHashAlg::Sha512 => 64,
+        }
+    }
+
+    fn hash(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            PcrHashAlg::Sha1 => Sha1::digest(data).to_vec(),
+            PcrHashAlg::Sha256 => Sha256::digest(data).to_vec(),
+            PcrHashAlg::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+/// Offset of the `CheckSum` field within the PE optional header. Coincidence
+/// of the field layout: PE32's 4-byte `BaseOfData` (present) and PE32+'s
+/// 4-byte-wider `ImageBase` (8 bytes instead of 4, `BaseOfData` absent)
+/// cancel out, so this offset is identical for both image types.
+const CHECKSUM_OFFSET_IN_OPTIONAL_HEADER: usize = 64;
+
+/// Offset of the `SizeOfHeaders` field within the PE optional header - same
+/// cancellation as [`CHECKSUM_OFFSET_IN_OPTIONAL_HEADER`] makes it identical
+/// for PE32 and PE32+.
+const SIZE_OF_HEADERS_OFFSET_IN_OPTIONAL_HEADER: usize = 60;
+
+/// Size in bytes of one `IMAGE_SECTION_HEADER` entry in the section table.
+const SECTION_HEADER_SIZE: usize = 40;
+
+/// Index of the Certificate Table entry within the optional header's
+/// DataDirectory array (Export, Import, Resource, Exception, **Security**, ...).
+const CERT_TABLE_DIRECTORY_INDEX: usize = 4;
+
+/// Compute the Authenticode digest of a PE/COFF image, following the
+/// Authenticode spec's header/section walk rather than a linear file hash:
+///
+/// 1. Hash from the start of the file through `SizeOfHeaders`, skipping the
+///    4-byte `CheckSum` field and the 8-byte Certificate Table DataDirectory
+///    entry (both can be populated, stripped, or resigned without changing
+///    what the binary executes).
+/// 2. Hash every section's raw data (`PointerToRawData`..`+SizeOfRawData`),
+///    sections visited in ascending `PointerToRawData` order - a linker is
+///    free to declare them in any order, and to leave gaps between them, so
+///    file order and declaration order both diverge from raw-data order in
+///    real images.
+/// 3. If any bytes remain after the last section's data, hash them too,
+///    except for the attribute certificate table itself (which the spec
+///    requires to be the last thing in the file).
+pub fn authenticode_digest(pe: &[u8], alg: PcrHashAlg) -> Result<Vec<u8>, String> {
+    if pe.len() < 0x40 || &pe[0..2] != b"MZ" {
+        return Err("Not a valid PE file (missing MZ signature)".to_string());
+    }
+
+    let pe_offset = u32::from_le_bytes(pe[0x3C..0x40].try_into().unwrap()) as usize;
+    if pe_offset + 24 > pe.len() || &pe[pe_offset..pe_offset + 4] != b"PE\0\0" {
+        return Err("Not a valid PE file (missing PE signature)".to_string());
+    }
+
+    let coff_start = pe_offset + 4;
+    let number_of_sections =
+        u16::from_le_bytes(pe[coff_start + 2..coff_start + 4].try_into().unwrap()) as usize;
+    let size_of_optional_header =
+        u16::from_le_bytes(pe[coff_start + 16..coff_start + 18].try_into().unwrap()) as usize;
+    let optional_header_start = coff_start + 20;
+    if size_of_optional_header < 2 || optional_header_start + size_of_optional_header > pe.len() {
+        return Err("Truncated or malformed optional header".to_string());
+    }
+
+    let magic = u16::from_le_bytes(
+        pe[optional_header_start..optional_header_start + 2]
+            .try_into()
+            .unwrap(),
+    );
+    let is_pe32_plus = match magic {
+        0x10b => false,
+        0x20b => true,
+        _ => return Err(format!("Unrecognized optional header magic: {:#x}", magic)),
+    };
+
+    let checksum_offset = optional_header_start + CHECKSUM_OFFSET_IN_OPTIONAL_HEADER;
+    let size_of_headers_offset = optional_header_start + SIZE_OF_HEADERS_OFFSET_IN_OPTIONAL_HEADER;
+    let size_of_headers =
+        u32::from_le_bytes(pe[size_of_headers_offset..size_of_headers_offset + 4].try_into().unwrap()) as usize;
+    if size_of_headers > pe.len() || size_of_headers < checksum_offset + 4 {
+        return Err("SizeOfHeaders is inconsistent with the file's actual size".to_string());
+    }
+
+    let data_directory_start = optional_header_start + if is_pe32_plus { 112 } else { 96 };
+    let cert_entry_offset = data_directory_start + CERT_TABLE_DIRECTORY_INDEX * 8;
+    if cert_entry_offset + 8 > pe.len() {
+        return Err("Optional header too small to contain a Certificate Table entry".to_string());
+    }
+
+    // The Certificate Table entry is the one DataDirectory field that holds a
+    // raw file offset instead of an RVA - the attribute certificate table
+    // isn't mapped into memory.
+    let cert_table_offset =
+        u32::from_le_bytes(pe[cert_entry_offset..cert_entry_offset + 4].try_into().unwrap()) as usize;
+    let cert_table_size =
+        u32::from_le_bytes(pe[cert_entry_offset + 4..cert_entry_offset + 8].try_into().unwrap()) as usize;
+
+    let section_table_start = optional_header_start + size_of_optional_header;
+    if section_table_start + number_of_sections * SECTION_HEADER_SIZE > pe.len() {
+        return Err("Truncated or malformed section table".to_string());
+    }
+
+    // Collect (PointerToRawData, SizeOfRawData) for every section with
+    // actual file content, then visit them in raw-data order - not the
+    // order they're declared in the section table.
+    let mut sections: Vec<(usize, usize)> = (0..number_of_sections)
+        .map(|i| {
+            let header = section_table_start + i * SECTION_HEADER_SIZE;
+            let size_of_raw_data =
+                u32::from_le_bytes(pe[header + 16..header + 20].try_into().unwrap()) as usize;
+            let pointer_to_raw_data =
+                u32::from_le_bytes(pe[header + 20..header + 24].try_into().unwrap()) as usize;
+            (pointer_to_raw_data, size_of_raw_data)
+        })
+        .filter(|(_, size)| *size > 0)
+        .collect();
+    sections.sort_by_key(|(ptr, _)| *ptr);
+
+    let mut hasher = RunningHash::new(alg);
+
+    // 1. Headers, rounded up to SizeOfHeaders, skipping CheckSum and the
+    // Certificate Table directory entry.
+    hasher.update(&pe[..checksum_offset]);
+    hasher.update(&pe[checksum_offset + 4..cert_entry_offset]);
+    let after_cert_entry = cert_entry_offset + 8;
+    hasher.update(&pe[after_cert_entry..size_of_headers]);
+
+    // 2. Every section's raw data, in PointerToRawData order.
+    let mut sum_of_bytes_hashed = size_of_headers;
+    for (ptr, size) in sections {
+        if ptr < sum_of_bytes_hashed {
+            // Overlapping/out-of-order section data would double-hash or
+            // desync the running total - refuse rather than produce a
+            // digest that silently doesn't match what the spec defines.
+            return Err(format!(
+                "Section at raw offset {} overlaps data already hashed up to {}",
+                ptr, sum_of_bytes_hashed
+            ));
+        }
+        let end = (ptr + size).min(pe.len());
+        if ptr < end {
+            hasher.update(&pe[ptr..end]);
+        }
+        sum_of_bytes_hashed = end;
+    }
+
+    // 3. Any trailing data after the last section, excluding the attribute
+    // certificate table the Certificate Table entry points at.
+    if cert_table_size > 0 && cert_table_offset >= sum_of_bytes_hashed && cert_table_offset <= pe.len() {
+        hasher.update(&pe[sum_of_bytes_hashed..cert_table_offset]);
+        let after_cert_table = (cert_table_offset + cert_table_size).min(pe.len());
+        hasher.update(&pe[after_cert_table..]);
+    } else if sum_of_bytes_hashed < pe.len() {
+        hasher.update(&pe[sum_of_bytes_hashed..]);
+    }
+
+    Ok(hasher.finish())
+}
+
+/// Incremental hasher over one of the three selectable algorithms, so
+/// [`authenticode_digest`] can feed it several disjoint byte ranges without
+/// concatenating them into one buffer first.
+enum RunningHash {
+    Sha1(Sha1),
+    Sha256(Sha256),
+    Sha512(Sha512),
+}
+
+impl RunningHash {
+    fn new(alg: PcrHashAlg) -> Self {
+        match alg {
+            PcrHashAlg::Sha1 => RunningHash::Sha1(Sha1::new()),
+            PcrHashAlg::Sha256 => RunningHash::Sha256(Sha256::new()),
+            PcrHashAlg::Sha512 => RunningHash::Sha512(Sha512::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            RunningHash::Sha1(h) => h.update(data),
+            RunningHash::Sha256(h) => h.update(data),
+            RunningHash::Sha512(h) => h.update(data),
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        match self {
+            RunningHash::Sha1(h) => h.finalize().to_vec(),
+            RunningHash::Sha256(h) => h.finalize().to_vec(),
+            RunningHash::Sha512(h) => h.finalize().to_vec(),
+        }
+    }
+}
+
+/// Replay one TPM `PCR_Extend`: `PCR_new = H(PCR_old || measurement)`.
+pub fn extend_pcr(pcr: &[u8], measurement: &[u8], alg: PcrHashAlg) -> Vec<u8> {
+    let mut data = Vec::with_capacity(pcr.len() + measurement.len());
+    data.extend_from_slice(pcr);
+    data.extend_from_slice(measurement);
+    alg.hash(&data)
+}
+
+/// One boot component's Authenticode digest, as measured on the way to the
+/// final PCR[4] value.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ComponentMeasurement {
+    pub path: String,
+    pub authenticode_digest: String,
+}
+
+/// The result of replaying PCR[4] across a boot chain: each component's
+/// individual Authenticode digest plus the folded PCR value a TPM would
+/// have measured after executing all of them in order.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BootMeasurement {
+    pub algorithm: String,
+    pub components: Vec<ComponentMeasurement>,
+    pub pcr4: String,
+}
+
+/// Hash every `(path, bytes)` component with Authenticode, in order, and
+/// fold each digest into PCR[4] starting from an all-zero value, the same
+/// way firmware measures a UEFI boot chain before handing off to the OS.
+pub fn measure_boot_chain(
+    components: &[(String, Vec<u8>)],
+    alg: PcrHashAlg,
+) -> Result<BootMeasurement, String> {
+    let mut pcr4 = vec![0u8; alg.digest_size()];
+    let mut measured = Vec::with_capacity(components.len());
+
+    for (path, bytes) in components {
+        let digest = authenticode_digest(bytes, alg)
+            .map_err(|e| format!("Failed to measure {}: {}", path, e))?;
+        pcr4 = extend_pcr(&pcr4, &digest, alg);
+        measured.push(ComponentMeasurement {
+            path: path.clone(),
+            authenticode_digest: hex::encode(&digest),
+        });
+    }
+
+    Ok(BootMeasurement {
+        algorithm: alg.name().to_string(),
+        components: measured,
+        pcr4: hex::encode(&pcr4),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a minimal but structurally valid PE32+ image: DOS header,
+    /// `PE\0\0` signature, a COFF header with no sections, and an optional
+    /// header sized to include a Certificate Table entry, followed by
+    /// `extra` trailing bytes that stand in for code/section data.
+    fn build_test_pe(checksum: u32, cert_offset: u32, cert_size: u32, extra: &[u8]) -> Vec<u8> {
+        let mut pe = vec![0u8; 0x40];
+        pe[0] = b'M';
+        pe[1] = b'Z';
+        let pe_offset = 0x40u32;
+        pe[0x3C..0x40].copy_from_slice(&pe_offset.to_le_bytes());
+
+        pe.extend_from_slice(b"PE\0\0");
+        // COFF header (20 bytes): machine, number_of_sections, timestamp,
+        // ptr_to_symtab, num_symbols, size_of_optional_header, characteristics.
+        let size_of_optional_header: u16 = 112 + 16 * 8; // PE32+ fixed fields + 16 data directories
+        pe.extend_from_slice(&0x8664u16.to_le_bytes()); // machine
+        pe.extend_from_slice(&0u16.to_le_bytes()); // number_of_sections
+        pe.extend_from_slice(&0u32.to_le_bytes()); // timestamp
+        pe.extend_from_slice(&0u32.to_le_bytes()); // ptr_to_symtab
+        pe.extend_from_slice(&0u32.to_le_bytes()); // num_symbols
+        pe.extend_from_slice(&size_of_optional_header.to_le_bytes());
+        pe.extend_from_slice(&0u16.to_le_bytes()); // characteristics
+
+        let optional_header_start = pe.len();
+        pe.extend_from_slice(&0x20bu16.to_le_bytes()); // magic: PE32+
+        pe.resize(optional_header_start + CHECKSUM_OFFSET_IN_OPTIONAL_HEADER, 0xAA);
+        pe.extend_from_slice(&checksum.to_le_bytes());
+        pe.resize(optional_header_start + 112, 0xBB);
+
+        let data_directory_start = pe.len();
+        assert_eq!(data_directory_start, optional_header_start + 112);
+        for i in 0..16u32 {
+            if i == CERT_TABLE_DIRECTORY_INDEX as u32 {
+                pe.extend_from_slice(&cert_offset.to_le_bytes());
+                pe.extend_from_slice(&cert_size.to_le_bytes());
+            } else {
+                pe.extend_from_slice(&0u32.to_le_bytes());
+                pe.extend_from_slice(&0u32.to_le_bytes());
+            }
+        }
+
+        // No sections in this fixture, so SizeOfHeaders covers everything up
+        // to where the caller's trailing bytes start.
+        let size_of_headers = pe.len() as u32;
+        let size_of_headers_offset = optional_header_start + SIZE_OF_HEADERS_OFFSET_IN_OPTIONAL_HEADER;
+        pe[size_of_headers_offset..size_of_headers_offset + 4]
+            .copy_from_slice(&size_of_headers.to_le_bytes());
+
+        pe.extend_from_slice(extra);
+        pe
+    }
+
+    #[test]
+    fn test_authenticode_digest_ignores_checksum_field() {
+        let cert_offset = 0; // no cert table in this test
+        let a = build_test_pe(0x1111_1111, cert_offset, 0, b"section data");
+        let b = build_test_pe(0x2222_2222, cert_offset, 0, b"section data");
+        assert_eq!(
+            authenticode_digest(&a, PcrHashAlg::Sha256).unwrap(),
+            authenticode_digest(&b, PcrHashAlg::Sha256).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_authenticode_digest_ignores_certificate_table_contents() {
+        let mut a = build_test_pe(0, 0, 0, b"section data");
+        let cert_offset = a.len() as u32;
+        a.extend_from_slice(b"signature-one!!!");
+        let expected_offset = cert_offset;
+
+        let mut b = build_test_pe(0, 0, 0, b"section data");
+        b.extend_from_slice(b"completely-diff!");
+
+        // Patch the cert table directory entry into both after the fact,
+        // since build_test_pe already fixed it at construction time above.
+        let a = patch_cert_entry(a, expected_offset, 16);
+        let b = patch_cert_entry(b, expected_offset, 16);
+
+        assert_eq!(
+            authenticode_digest(&a, PcrHashAlg::Sha256).unwrap(),
+            authenticode_digest(&b, PcrHashAlg::Sha256).unwrap()
+        );
+    }
+
+    fn patch_cert_entry(mut pe: Vec<u8>, offset: u32, size: u32) -> Vec<u8> {
+        let pe_offset = u32::from_le_bytes(pe[0x3C..0x40].try_into().unwrap()) as usize;
+        let coff_start = pe_offset + 4;
+        let optional_header_start = coff_start + 20;
+        let cert_entry_offset = optional_header_start + 112 + CERT_TABLE_DIRECTORY_INDEX * 8;
+        pe[cert_entry_offset..cert_entry_offset + 4].copy_from_slice(&offset.to_le_bytes());
+        pe[cert_entry_offset + 4..cert_entry_offset + 8].copy_from_slice(&size.to_le_bytes());
+        pe
+    }
+
+    #[test]
+    fn test_authenticode_digest_detects_code_changes() {
+        let a = build_test_pe(0, 0, 0, b"section data one");
+        let b = build_test_pe(0, 0, 0, b"section data two");
+        assert_ne!(
+            authenticode_digest(&a, PcrHashAlg::Sha256).unwrap(),
+            authenticode_digest(&b, PcrHashAlg::Sha256).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_authenticode_digest_rejects_non_pe_files() {
+        assert!(authenticode_digest(b"not a pe file", PcrHashAlg::Sha256).is_err());
+    }
+
+    #[test]
+    fn test_extend_pcr_matches_manual_concatenation() {
+        let pcr = vec![0u8; 32];
+        let measurement = Sha256::digest(b"component").to_vec();
+        let extended = extend_pcr(&pcr, &measurement, PcrHashAlg::Sha256);
+
+        let mut expected_input = pcr.clone();
+        expected_input.extend_from_slice(&measurement);
+        let expected = Sha256::digest(&expected_input).to_vec();
+
+        assert_eq!(extended, expected);
+    }
+
+    #[test]
+    fn test_measure_boot_chain_folds_components_in_order() {
+        let shim = build_test_pe(0, 0, 0, b"shim code");
+        let grub = build_test_pe(0, 0, 0, b"grub code");
+        let components = vec![
+            ("shimx64.efi".to_string(), shim.clone()),
+            ("grubx64.efi".to_string(), grub.clone()),
+        ];
+
+        let result = measure_boot_chain(&components, PcrHashAlg::Sha256).unwrap();
+        assert_eq!(result.components.len(), 2);
+
+        let shim_digest = authenticode_digest(&shim, PcrHashAlg::Sha256).unwrap();
+        let grub_digest = authenticode_digest(&grub, PcrHashAlg::Sha256).unwrap();
+        let pcr_after_shim = extend_pcr(&vec![0u8; 32], &shim_digest, PcrHashAlg::Sha256);
+        let pcr_after_grub = extend_pcr(&pcr_after_shim, &grub_digest, PcrHashAlg::Sha256);
+
+        assert_eq!(result.pcr4, hex::encode(&pcr_after_grub));
+    }
+}
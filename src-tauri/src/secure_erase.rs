@@ -1,16 +1,29 @@
 //! Secure erase module for n01d-forge
-//! 
+//!
 //! Implements various secure erase methods:
 //! - Zero fill
 //! - Random data
 //! - DoD 5220.22-M (3-pass)
 //! - Gutmann method (35-pass)
+//! - Hardware sanitize (discard/TRIM, NVMe format, ATA Secure Erase) for flash storage
+//! - Shred-style randomized pattern schedule with optional verification
+//! - Progress streaming and cooperative cancellation for long-running erases
+//! - Signed, machine-readable certificates of erasure for compliance records
 
 use std::fs::{File, OpenOptions};
-use std::io::{Write, Seek, SeekFrom};
+use std::io::{Write, Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::Instant;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use rand::RngCore;
+use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 
+#[cfg(target_os = "linux")]
+use std::os::unix::io::AsRawFd;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum SecureEraseMethod {
     /// Single pass of zeros
@@ -23,6 +36,15 @@ pub enum SecureEraseMethod {
     Gutmann,
     /// Custom number of random passes
     CustomRandom(u8),
+    /// Hardware-assisted sanitize for flash storage: block discard/secure
+    /// discard, NVMe format, or ATA Secure Erase, instead of an overwrite.
+    HardwareSanitize,
+    /// GNU shred-style schedule: `passes` total writes drawn from a
+    /// shuffled subset of shred's canonical fixed patterns interleaved with
+    /// random passes. `verify` reads back every non-random pass to confirm
+    /// it landed; `final_zero` appends a trailing zero pass so the drive
+    /// doesn't visibly look shredded.
+    Shred { passes: u8, verify: bool, final_zero: bool },
 }
 
 impl SecureEraseMethod {
@@ -33,9 +55,13 @@ impl SecureEraseMethod {
             SecureEraseMethod::DoD => 3,
             SecureEraseMethod::Gutmann => 35,
             SecureEraseMethod::CustomRandom(n) => *n,
+            SecureEraseMethod::HardwareSanitize => 1,
+            SecureEraseMethod::Shred { passes, final_zero, .. } => {
+                passes + if *final_zero { 1 } else { 0 }
+            }
         }
     }
-    
+
     pub fn name(&self) -> &'static str {
         match self {
             SecureEraseMethod::Zeros => "Zero Fill",
@@ -43,61 +69,790 @@ impl SecureEraseMethod {
             SecureEraseMethod::DoD => "DoD 5220.22-M",
             SecureEraseMethod::Gutmann => "Gutmann (35-pass)",
             SecureEraseMethod::CustomRandom(_) => "Custom Random",
+            SecureEraseMethod::HardwareSanitize => "Hardware Sanitize (TRIM/Secure Erase)",
+            SecureEraseMethod::Shred { .. } => "Shred (randomized)",
+        }
+    }
+
+    /// The NIST SP 800-88 Rev. 1 sanitization category this method achieves.
+    /// Overwrite-based methods - however many passes - are `Clear`: 800-88
+    /// only credits `Purge` to techniques that defeat state-of-the-art
+    /// laboratory recovery (ATA Secure Erase, NVMe sanitize/format, crypto
+    /// erase, degaussing), which is exactly what [`HardwareSanitize`] does.
+    ///
+    /// [`HardwareSanitize`]: SecureEraseMethod::HardwareSanitize
+    pub fn nist_category(&self) -> SanitizationCategory {
+        match self {
+            SecureEraseMethod::HardwareSanitize => SanitizationCategory::Purge,
+            _ => SanitizationCategory::Clear,
+        }
+    }
+}
+
+/// NIST SP 800-88 Rev. 1 sanitization category implied by a [`SecureEraseMethod`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SanitizationCategory {
+    Clear,
+    Purge,
+}
+
+/// A single pass as it actually ran, recorded into an [`ErasureCertificate`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassRecord {
+    pub pass_index: u8,
+    pub pattern: String,
+    /// `Some(true)` if this pass was read back and matched; `None` if it
+    /// wasn't verified (random passes never are - there's nothing recorded
+    /// to compare against).
+    pub verified: Option<bool>,
+}
+
+/// An ed25519 signature over an [`ErasureCertificate`]'s canonicalized JSON
+/// (the certificate with this field itself set to `None`), so the signature
+/// doesn't need to cover its own bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateSignature {
+    pub algorithm: String, // currently only "ed25519"
+    pub public_key: String, // hex-encoded
+    pub signature: String,  // hex-encoded
+}
+
+/// A machine-readable, optionally-signed record that a device was erased:
+/// what it was, how it was erased, what NIST category that achieves, the
+/// effective pass schedule with per-pass verification results, and when it
+/// happened. Returned by [`secure_erase_drive`] for compliance workflows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasureCertificate {
+    pub device: String,
+    pub model: String,
+    pub serial: String,
+    pub size_bytes: u64,
+    pub method: String,
+    pub nist_category: SanitizationCategory,
+    pub passes: Vec<PassRecord>,
+    pub started_at_unix: u64,
+    pub completed_at_unix: u64,
+    pub tool_version: String,
+    pub signature: Option<CertificateSignature>,
+}
+
+/// Whether `device` is backed by spinning media, read from
+/// `/sys/block/<dev>/queue/rotational`. Defaults to `true` (rotational) when
+/// the attribute can't be read, since that's the safer assumption for
+/// steering users away from a discard-based erase.
+#[cfg(target_os = "linux")]
+pub fn is_rotational(device: &str) -> bool {
+    let name = device.trim_start_matches("/dev/");
+    let path = format!("/sys/block/{}/queue/rotational", name);
+    std::fs::read_to_string(path)
+        .map(|s| s.trim() == "1")
+        .unwrap_or(true)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn is_rotational(_device: &str) -> bool {
+    true
+}
+
+/// Why [`preflight_check`] refused to let an erase proceed.
+#[derive(Debug)]
+pub enum EraseError {
+    /// The device itself (or one of its partitions) is mounted.
+    Mounted(String),
+    /// A device-mapper (or other) holder built on top of this device is mounted.
+    HolderMounted(String),
+    /// The device backs the running root filesystem.
+    RootDevice,
+    /// Couldn't inspect the device or system mount state.
+    Io(String),
+    /// The caller's [`CancellationToken`] was tripped mid-erase.
+    Cancelled,
+}
+
+impl std::fmt::Display for EraseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EraseError::Mounted(mp) => write!(f, "device is mounted at {}", mp),
+            EraseError::HolderMounted(h) => write!(f, "device is in use by {}, which is mounted", h),
+            EraseError::RootDevice => write!(f, "device backs the running root filesystem"),
+            EraseError::Io(e) => write!(f, "failed to inspect device: {}", e),
+            EraseError::Cancelled => write!(f, "erase cancelled"),
+        }
+    }
+}
+
+impl From<EraseError> for String {
+    fn from(e: EraseError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Refuse to erase a device that's mounted, holds a mounted filesystem
+/// (e.g. is the physical volume under a mounted LVM/LUKS mapping), or backs
+/// the running root filesystem. `force` bypasses every check - callers
+/// should only set it after a separate, explicit user confirmation.
+#[cfg(target_os = "linux")]
+pub fn preflight_check(device: &str, force: bool) -> Result<(), EraseError> {
+    if force {
+        return Ok(());
+    }
+
+    let name = device.trim_start_matches("/dev/").to_string();
+    let mounts = std::fs::read_to_string("/proc/mounts").map_err(|e| EraseError::Io(e.to_string()))?;
+    let mounted_sources: Vec<(String, String)> = mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let source = fields.next()?.strip_prefix("/dev/")?.to_string();
+            let mount_point = fields.next()?.to_string();
+            Some((source, mount_point))
+        })
+        .collect();
+
+    // The disk or any of its partitions is mounted directly.
+    for (source, mount_point) in &mounted_sources {
+        if *source == name || (source.starts_with(&name) && source[name.len()..].chars().next().map_or(false, |c| c.is_ascii_digit() || c == 'p')) {
+            return Err(EraseError::Mounted(mount_point.clone()));
+        }
+    }
+
+    // Device-mapper (or other) holders stacked on top of this device - walk
+    // /sys/block/<dev>/holders and its partitions' holders for anything mounted.
+    for holder in collect_holders(&name) {
+        if let Some((_, mount_point)) = mounted_sources.iter().find(|(source, _)| *source == holder) {
+            return Err(EraseError::HolderMounted(holder));
+        }
+    }
+
+    if let Some(root_source) = mounted_sources.iter().find(|(_, mp)| mp == "/").map(|(s, _)| s.clone()) {
+        let root_disk = base_disk_name(&root_source);
+        if root_disk == name {
+            return Err(EraseError::RootDevice);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn preflight_check(_device: &str, force: bool) -> Result<(), EraseError> {
+    if force {
+        Ok(())
+    } else {
+        Err(EraseError::Io("mount safety checks are only implemented on Linux".to_string()))
+    }
+}
+
+/// Strip a partition suffix (`sda1` -> `sda`, `nvme0n1p1` -> `nvme0n1`) to
+/// find the whole-disk block device a partition belongs to.
+#[cfg(target_os = "linux")]
+fn base_disk_name(part: &str) -> String {
+    if let Some(idx) = part.rfind('p') {
+        let (head, tail) = part.split_at(idx);
+        if head.contains("nvme") && tail[1..].chars().all(|c| c.is_ascii_digit()) && !tail[1..].is_empty() {
+            return head.to_string();
+        }
+    }
+    part.trim_end_matches(|c: char| c.is_ascii_digit()).to_string()
+}
+
+/// All holder device names for `disk` and each of its partitions, gathered
+/// from `/sys/block/<dev>/holders` (covers LVM/LUKS/MD mappings built on
+/// top of the disk or a partition of it).
+#[cfg(target_os = "linux")]
+fn collect_holders(disk: &str) -> Vec<String> {
+    let mut holders = Vec::new();
+    let disk_path = format!("/sys/block/{}", disk);
+
+    let mut candidates = vec![disk_path.clone()];
+    if let Ok(entries) = std::fs::read_dir(&disk_path) {
+        for entry in entries.flatten() {
+            let part_name = entry.file_name().to_string_lossy().to_string();
+            if part_name.starts_with(disk) && entry.path().join("partition").exists() {
+                candidates.push(entry.path().to_string_lossy().to_string());
+            }
+        }
+    }
+
+    for candidate in candidates {
+        let holders_path = format!("{}/holders", candidate);
+        if let Ok(entries) = std::fs::read_dir(&holders_path) {
+            for entry in entries.flatten() {
+                holders.push(entry.file_name().to_string_lossy().to_string());
+            }
         }
     }
+
+    holders
 }
 
 /// Perform secure erase on a drive
 pub async fn secure_erase_drive(
     device: &str,
     method: SecureEraseMethod,
-) -> Result<(), String> {
+    force: bool,
+) -> Result<ErasureCertificate, String> {
+    preflight_check(device, force)?;
+
+    let started_at_unix = unix_now();
+    let model = read_sysfs_device_attr(device, "model");
+    let serial = read_sysfs_device_attr(device, "serial");
+
+    // HardwareSanitize never touches the overwrite path below - it asks the
+    // device to destroy its own mapping table instead.
+    if matches!(method, SecureEraseMethod::HardwareSanitize) {
+        hardware_sanitize_drive(device).await?;
+        return Ok(ErasureCertificate {
+            device: device.to_string(),
+            model,
+            serial,
+            size_bytes: get_device_size(device).unwrap_or(0),
+            method: method.name().to_string(),
+            nist_category: method.nist_category(),
+            passes: vec![PassRecord {
+                pass_index: 0,
+                pattern: "hardware-sanitize".to_string(),
+                verified: None,
+            }],
+            started_at_unix,
+            completed_at_unix: unix_now(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            signature: None,
+        });
+    }
+
     // Get device size
     let size = get_device_size(device)?;
-    
+
     // Open device for writing
     let mut file = OpenOptions::new()
         .write(true)
         .open(device)
         .map_err(|e| format!("Failed to open device: {}", e))?;
-    
-    match method {
-        SecureEraseMethod::Zeros => {
-            write_pattern(&mut file, size, PatternType::Zeros)?;
-        },
-        SecureEraseMethod::Random => {
-            write_pattern(&mut file, size, PatternType::Random)?;
-        },
-        SecureEraseMethod::DoD => {
-            // DoD 5220.22-M: Pass 1 - zeros, Pass 2 - ones, Pass 3 - random
-            write_pattern(&mut file, size, PatternType::Zeros)?;
-            file.seek(SeekFrom::Start(0)).map_err(|e| format!("Seek failed: {}", e))?;
-            write_pattern(&mut file, size, PatternType::Ones)?;
+
+    let mut passes_record = Vec::new();
+
+    for (pass_index, pattern, verify_this_pass) in build_pass_plan(&method) {
+        file.seek(SeekFrom::Start(0)).map_err(|e| format!("Seek failed: {}", e))?;
+        write_pattern(&mut file, size, pattern)?;
+
+        let verified = if verify_this_pass {
+            file.sync_all().map_err(|e| format!("Sync failed: {}", e))?;
             file.seek(SeekFrom::Start(0)).map_err(|e| format!("Seek failed: {}", e))?;
-            write_pattern(&mut file, size, PatternType::Random)?;
-        },
-        SecureEraseMethod::Gutmann => {
-            // Gutmann 35-pass method
-            for pass in 0..35 {
-                file.seek(SeekFrom::Start(0)).map_err(|e| format!("Seek failed: {}", e))?;
-                let pattern = get_gutmann_pattern(pass);
-                write_pattern(&mut file, size, pattern)?;
-            }
-        },
-        SecureEraseMethod::CustomRandom(passes) => {
-            for _ in 0..passes {
-                file.seek(SeekFrom::Start(0)).map_err(|e| format!("Seek failed: {}", e))?;
-                write_pattern(&mut file, size, PatternType::Random)?;
-            }
-        },
+            verify_pattern(&mut file, size, pattern)?;
+            Some(true)
+        } else {
+            None
+        };
+
+        passes_record.push(PassRecord {
+            pass_index,
+            pattern: pattern_label(pattern),
+            verified,
+        });
     }
-    
+
     // Sync to ensure all data is written
     file.sync_all().map_err(|e| format!("Sync failed: {}", e))?;
-    
+
+    Ok(ErasureCertificate {
+        device: device.to_string(),
+        model,
+        serial,
+        size_bytes: size,
+        method: method.name().to_string(),
+        nist_category: method.nist_category(),
+        passes: passes_record,
+        started_at_unix,
+        completed_at_unix: unix_now(),
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        signature: None,
+    })
+}
+
+/// Flatten a [`SecureEraseMethod`] into its effective pass schedule as
+/// `(pass_index, pattern, verify_this_pass)` tuples, shared by
+/// [`secure_erase_drive`] and [`secure_erase_drive_with_progress`] so the
+/// schedule only has to be derived in one place. `HardwareSanitize` isn't an
+/// overwrite method, so it flattens to no passes - callers handle it before
+/// reaching this.
+fn build_pass_plan(method: &SecureEraseMethod) -> Vec<(u8, PatternType, bool)> {
+    match *method {
+        SecureEraseMethod::Zeros => vec![(0, PatternType::Zeros, false)],
+        SecureEraseMethod::Random => vec![(0, PatternType::Random, false)],
+        SecureEraseMethod::DoD => vec![
+            // DoD 5220.22-M: Pass 1 - zeros, Pass 2 - ones, Pass 3 - random
+            (0, PatternType::Zeros, false),
+            (1, PatternType::Ones, false),
+            (2, PatternType::Random, false),
+        ],
+        SecureEraseMethod::Gutmann => (0..35).map(|p| (p, get_gutmann_pattern(p), false)).collect(),
+        SecureEraseMethod::CustomRandom(passes) => {
+            (0..passes).map(|p| (p, PatternType::Random, false)).collect()
+        }
+        SecureEraseMethod::Shred { passes, verify, final_zero } => {
+            let mut schedule: Vec<(u8, PatternType, bool)> = build_shred_schedule(passes)
+                .into_iter()
+                .enumerate()
+                .map(|(i, pattern)| (i as u8, pattern, verify && !matches!(pattern, PatternType::Random)))
+                .collect();
+            if final_zero {
+                let next_index = schedule.len() as u8;
+                schedule.push((next_index, PatternType::Zeros, verify));
+            }
+            schedule
+        }
+        SecureEraseMethod::HardwareSanitize => Vec::new(),
+    }
+}
+
+/// Human-readable name for a pattern, recorded into an [`ErasureCertificate`]'s
+/// [`PassRecord`]s.
+fn pattern_label(pattern: PatternType) -> String {
+    match pattern {
+        PatternType::Zeros => "zeros".to_string(),
+        PatternType::Ones => "ones".to_string(),
+        PatternType::Random => "random".to_string(),
+        PatternType::Fixed(p) => format!("fixed(0x{:02x} 0x{:02x} 0x{:02x})", p[0], p[1], p[2]),
+    }
+}
+
+/// Read `/sys/block/<dev>/device/<attr>`, trimmed, or an empty string if it
+/// can't be read (not a real block device, attribute absent, or non-Linux).
+#[cfg(target_os = "linux")]
+fn read_sysfs_device_attr(device: &str, attr: &str) -> String {
+    let name = device.trim_start_matches("/dev/");
+    let path = format!("/sys/block/{}/device/{}", name, attr);
+    std::fs::read_to_string(path).unwrap_or_default().trim().to_string()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_sysfs_device_attr(_device: &str, _attr: &str) -> String {
+    String::new()
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Sign `cert` with `signing_key`: the signature covers `cert`'s
+/// canonicalized JSON with `signature` itself left `None`, so the certificate
+/// can be independently re-verified later without the signing key.
+pub fn sign_certificate(cert: &ErasureCertificate, signing_key: &SigningKey) -> Result<ErasureCertificate, String> {
+    let mut unsigned = cert.clone();
+    unsigned.signature = None;
+    let json = serde_json::to_string(&unsigned)
+        .map_err(|e| format!("Failed to serialize certificate: {}", e))?;
+
+    let signature: Signature = signing_key.sign(json.as_bytes());
+
+    let mut signed = unsigned;
+    signed.signature = Some(CertificateSignature {
+        algorithm: "ed25519".to_string(),
+        public_key: hex::encode(signing_key.verifying_key().to_bytes()),
+        signature: hex::encode(signature.to_bytes()),
+    });
+
+    Ok(signed)
+}
+
+/// Recompute a signed certificate's canonicalized JSON and check it against
+/// the embedded signature and public key.
+pub fn verify_certificate(cert: &ErasureCertificate) -> Result<bool, String> {
+    let sig = cert.signature.as_ref().ok_or_else(|| "Certificate is not signed".to_string())?;
+    if sig.algorithm != "ed25519" {
+        return Err(format!("Unsupported signature algorithm: {}", sig.algorithm));
+    }
+
+    let mut unsigned = cert.clone();
+    unsigned.signature = None;
+    let json = serde_json::to_string(&unsigned)
+        .map_err(|e| format!("Failed to serialize certificate: {}", e))?;
+
+    let public_key_bytes: [u8; 32] = hex::decode(&sig.public_key)
+        .map_err(|e| format!("Invalid public key: {}", e))?
+        .try_into()
+        .map_err(|_| "Invalid public key length".to_string())?;
+    let verifying_key = VerifyingKey::from_bytes(&public_key_bytes)
+        .map_err(|e| format!("Invalid public key: {}", e))?;
+
+    let signature_bytes: [u8; 64] = hex::decode(&sig.signature)
+        .map_err(|e| format!("Invalid signature: {}", e))?
+        .try_into()
+        .map_err(|_| "Invalid signature length".to_string())?;
+    let signature = Signature::from_bytes(&signature_bytes);
+
+    Ok(verifying_key.verify(json.as_bytes(), &signature).is_ok())
+}
+
+/// Write a certificate (signed or not) to `path` as pretty-printed JSON, for
+/// callers that want a standalone compliance record alongside the returned
+/// value.
+pub fn write_certificate(cert: &ErasureCertificate, path: &str) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(cert)
+        .map_err(|e| format!("Failed to serialize certificate: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write certificate to {}: {}", path, e))
+}
+
+/// A shared, cloneable stop flag a caller can trip from another task to
+/// abort a running [`secure_erase_drive_with_progress`] call. Mirrors the
+/// `Arc<AtomicBool>` cancel flag `erase.rs`'s worker hands back to its UI
+/// thread, just wrapped so the intent reads clearly at the call site.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    /// Wrap an existing shared flag instead of allocating a new one, so a
+    /// caller that already has an `Arc<AtomicBool>` cancel flag (e.g. the
+    /// app's single shared `cancel_flag`) can drive this token with it.
+    pub fn from_shared(flag: Arc<AtomicBool>) -> Self {
+        Self(flag)
+    }
+
+    /// Request that the erase stop at the next checkpoint.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// A snapshot of an in-progress erase, sent over the progress channel after
+/// every buffer write so a caller can render a progress bar without polling
+/// shared state. `throughput_mbps` and `eta_seconds` are computed from bytes
+/// written across the whole job (every pass) against elapsed wall-clock time
+/// since the erase started.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EraseProgress {
+    pub pass_index: u8,
+    pub total_passes: u8,
+    pub bytes_written_this_pass: u64,
+    pub total_size: u64,
+    pub throughput_mbps: f64,
+    pub eta_seconds: u64,
+}
+
+/// Like [`secure_erase_drive`], but streams an [`EraseProgress`] snapshot
+/// over `progress` after every buffer write and checks `cancel` at each
+/// buffer iteration and between passes, so a caller can abort a multi-hour
+/// Gutmann-style run cleanly. On cancellation the device is flushed before
+/// returning `Err(EraseError::Cancelled)`, so no partially-buffered write is
+/// left in an undefined state.
+pub async fn secure_erase_drive_with_progress(
+    device: &str,
+    method: SecureEraseMethod,
+    force: bool,
+    progress: Sender<EraseProgress>,
+    cancel: CancellationToken,
+) -> Result<ErasureCertificate, EraseError> {
+    preflight_check(device, force)?;
+
+    let started_at_unix = unix_now();
+    let model = read_sysfs_device_attr(device, "model");
+    let serial = read_sysfs_device_attr(device, "serial");
+
+    if matches!(method, SecureEraseMethod::HardwareSanitize) {
+        hardware_sanitize_drive(device).await.map_err(EraseError::Io)?;
+        return Ok(ErasureCertificate {
+            device: device.to_string(),
+            model,
+            serial,
+            size_bytes: get_device_size(device).unwrap_or(0),
+            method: method.name().to_string(),
+            nist_category: method.nist_category(),
+            passes: vec![PassRecord {
+                pass_index: 0,
+                pattern: "hardware-sanitize".to_string(),
+                verified: None,
+            }],
+            started_at_unix,
+            completed_at_unix: unix_now(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            signature: None,
+        });
+    }
+
+    let size = get_device_size(device).map_err(EraseError::Io)?;
+    let total_passes = method.passes();
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .open(device)
+        .map_err(|e| EraseError::Io(format!("Failed to open device: {}", e)))?;
+
+    // Flatten every method into a flat pass list of (index, pattern, verify-this-pass)
+    // so the progress/cancel plumbing below only has to walk it once.
+    let passes = build_pass_plan(&method);
+
+    let start = Instant::now();
+    let job_total_bytes = size.saturating_mul(total_passes as u64);
+    let mut job_bytes_done = 0u64;
+    let mut passes_record = Vec::new();
+
+    for (pass_index, pattern, verify_this_pass) in passes {
+        if cancel.is_cancelled() {
+            file.sync_all().ok();
+            return Err(EraseError::Cancelled);
+        }
+
+        file.seek(SeekFrom::Start(0)).map_err(|e| EraseError::Io(format!("Seek failed: {}", e)))?;
+        write_pattern_with_progress(
+            &mut file,
+            size,
+            pattern,
+            pass_index,
+            total_passes,
+            &progress,
+            &cancel,
+            &start,
+            &mut job_bytes_done,
+            job_total_bytes,
+        )?;
+
+        let verified = if verify_this_pass {
+            file.sync_all().map_err(|e| EraseError::Io(format!("Sync failed: {}", e)))?;
+            file.seek(SeekFrom::Start(0)).map_err(|e| EraseError::Io(format!("Seek failed: {}", e)))?;
+            verify_pattern(&mut file, size, pattern).map_err(EraseError::Io)?;
+            Some(true)
+        } else {
+            None
+        };
+
+        passes_record.push(PassRecord {
+            pass_index,
+            pattern: pattern_label(pattern),
+            verified,
+        });
+    }
+
+    file.sync_all().map_err(|e| EraseError::Io(format!("Sync failed: {}", e)))?;
+
+    Ok(ErasureCertificate {
+        device: device.to_string(),
+        model,
+        serial,
+        size_bytes: size,
+        method: method.name().to_string(),
+        nist_category: method.nist_category(),
+        passes: passes_record,
+        started_at_unix,
+        completed_at_unix: unix_now(),
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        signature: None,
+    })
+}
+
+/// Ask the device to destroy its own flash translation layer mapping instead
+/// of overwriting every block ourselves. NVMe devices go straight to an NVMe
+/// sanitize-format; any other device tries, in order: secure discard, plain
+/// discard, ATA Secure Erase. Returns a descriptive error if the device
+/// supports none of these.
+#[cfg(target_os = "linux")]
+async fn hardware_sanitize_drive(device: &str) -> Result<(), String> {
+    let name = device.trim_start_matches("/dev/");
+
+    if name.starts_with("nvme") {
+        return nvme_sanitize(device).await;
+    }
+
+    let size = get_device_size(device)?;
+
+    {
+        let file = OpenOptions::new()
+            .write(true)
+            .open(device)
+            .map_err(|e| format!("Failed to open device: {}", e))?;
+        let fd = file.as_raw_fd();
+
+        if linux_ioctl::discard_range(fd, linux_ioctl::BLKSECDISCARD, 0, size) {
+            return Ok(());
+        }
+        if linux_ioctl::discard_range(fd, linux_ioctl::BLKDISCARD, 0, size) {
+            return Ok(());
+        }
+    }
+
+    if ata_secure_erase(device).await.is_ok() {
+        return Ok(());
+    }
+
+    Err(format!(
+        "{} supports neither secure discard, TRIM, nor ATA Secure Erase; use an overwrite method instead",
+        device
+    ))
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn hardware_sanitize_drive(_device: &str) -> Result<(), String> {
+    Err("Hardware-assisted sanitize is only implemented on Linux".to_string())
+}
+
+#[cfg(target_os = "linux")]
+mod linux_ioctl {
+    use std::os::unix::io::RawFd;
+
+    // From <linux/fs.h>: BLKDISCARD = _IO(0x12,119), BLKSECDISCARD = _IO(0x12,125)
+    pub const BLKDISCARD: libc::c_ulong = 0x1277;
+    pub const BLKSECDISCARD: libc::c_ulong = 0x127D;
+    // BLKGETSIZE64 = _IOR(0x12,114,size_t)
+    pub const BLKGETSIZE64: libc::c_ulong = 0x80081272;
+
+    /// Issue a whole-range discard ioctl. Returns `true` on success, `false`
+    /// if the kernel or device rejected it (unsupported, not a block device,
+    /// etc.) so the caller can fall through to the next method.
+    pub fn discard_range(fd: RawFd, request: libc::c_ulong, offset: u64, len: u64) -> bool {
+        let range: [u64; 2] = [offset, len];
+        unsafe { libc::ioctl(fd, request, range.as_ptr()) == 0 }
+    }
+
+    /// Read the device's byte size via `BLKGETSIZE64`. Returns `None` if the
+    /// ioctl isn't supported on this fd (e.g. it's a regular file, not a
+    /// block device).
+    pub fn get_size64(fd: RawFd) -> Option<u64> {
+        let mut size: u64 = 0;
+        let ret = unsafe { libc::ioctl(fd, BLKGETSIZE64, &mut size as *mut u64) };
+        if ret == 0 {
+            Some(size)
+        } else {
+            None
+        }
+    }
+}
+
+/// Byte size of `device` without shelling out: `BLKGETSIZE64` on the open
+/// fd, falling back to seeking to the end of the file for anything that
+/// isn't a block device (e.g. a disk image during tests).
+#[cfg(target_os = "linux")]
+fn native_device_size_linux(device: &str) -> Result<u64, String> {
+    let mut file = File::open(device).map_err(|e| format!("Failed to open device: {}", e))?;
+    let fd = file.as_raw_fd();
+
+    if let Some(size) = linux_ioctl::get_size64(fd) {
+        return Ok(size);
+    }
+
+    file.seek(SeekFrom::End(0)).map_err(|e| format!("Failed to seek device: {}", e))
+}
+
+#[cfg(target_os = "linux")]
+async fn nvme_sanitize(device: &str) -> Result<(), String> {
+    use std::process::Command;
+
+    let output = Command::new("nvme")
+        .args(["format", device, "--ses=1"])
+        .output()
+        .map_err(|e| format!("Failed to run nvme format: {}", e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "nvme format --ses=1 failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
+
+#[cfg(target_os = "linux")]
+/// Parse `hdparm -I`'s `Security:` block and refuse to proceed unless the
+/// feature set is supported and not frozen - setting a password on a frozen
+/// drive (common after a BIOS/firmware lock pending a power cycle) would
+/// leave it security-locked with no way to undo it short of a power cycle.
+fn check_ata_security_available(device: &str) -> Result<(), String> {
+    use std::process::Command;
+
+    let info = Command::new("hdparm")
+        .args(["-I", device])
+        .output()
+        .map_err(|e| format!("Failed to run hdparm -I: {}", e))?;
+    let text = String::from_utf8_lossy(&info.stdout);
+
+    let security_lines: Vec<&str> = text
+        .lines()
+        .skip_while(|line| line.trim() != "Security:")
+        .skip(1)
+        .take_while(|line| line.starts_with(char::is_whitespace))
+        .map(|line| line.trim())
+        .collect();
+
+    if !security_lines.iter().any(|line| *line == "supported") {
+        return Err(format!("{} does not report ATA Security feature set support", device));
+    }
+    if security_lines.iter().any(|line| *line == "frozen") {
+        return Err(format!(
+            "{} reports ATA Security as frozen (needs a power cycle before it can be unlocked); refusing to set a password",
+            device
+        ));
+    }
+    if security_lines.iter().any(|line| *line == "enabled") {
+        return Err(format!(
+            "{} already has ATA Security enabled with an unknown password; refusing to overwrite it",
+            device
+        ));
+    }
+
     Ok(())
 }
 
+async fn ata_secure_erase(device: &str) -> Result<(), String> {
+    use std::process::Command;
+
+    const UNLOCK_PASSWORD: &str = "p";
+
+    check_ata_security_available(device)?;
+
+    let set_pass = Command::new("hdparm")
+        .args(["--user-master", "u", "--security-set-pass", UNLOCK_PASSWORD, device])
+        .output()
+        .map_err(|e| format!("Failed to run hdparm --security-set-pass: {}", e))?;
+    if !set_pass.status.success() {
+        return Err(format!(
+            "hdparm --security-set-pass failed: {}",
+            String::from_utf8_lossy(&set_pass.stderr)
+        ));
+    }
+
+    let erase = Command::new("hdparm")
+        .args(["--user-master", "u", "--security-erase", UNLOCK_PASSWORD, device])
+        .output()
+        .map_err(|e| format!("Failed to run hdparm --security-erase: {}", e))?;
+    if erase.status.success() {
+        return Ok(());
+    }
+
+    let erase_err = format!(
+        "hdparm --security-erase failed: {}",
+        String::from_utf8_lossy(&erase.stderr)
+    );
+
+    // The password is now set on the drive - leaving it there would
+    // security-lock it until someone manually runs --security-disable. Undo
+    // it ourselves so a failed erase doesn't brick the device.
+    let disable = Command::new("hdparm")
+        .args(["--user-master", "u", "--security-disable", UNLOCK_PASSWORD, device])
+        .output();
+
+    match disable {
+        Ok(out) if out.status.success() => Err(erase_err),
+        Ok(out) => Err(format!(
+            "{erase_err}; additionally failed to undo --security-set-pass, device may be SECURITY-LOCKED under password \"{UNLOCK_PASSWORD}\": {}",
+            String::from_utf8_lossy(&out.stderr)
+        )),
+        Err(e) => Err(format!(
+            "{erase_err}; additionally failed to run hdparm --security-disable to undo --security-set-pass, device may be SECURITY-LOCKED under password \"{UNLOCK_PASSWORD}\": {e}"
+        )),
+    }
+}
+
 #[derive(Clone, Copy)]
 enum PatternType {
     Zeros,
@@ -146,10 +901,202 @@ fn write_pattern(
         
         bytes_written += to_write as u64;
     }
-    
+
+    Ok(())
+}
+
+/// Same buffer-filling loop as [`write_pattern`], but checks `cancel` before
+/// every chunk and reports an [`EraseProgress`] snapshot after every chunk.
+/// `job_bytes_done`/`job_total_bytes` track the whole multi-pass job so the
+/// throughput/ETA estimate stays stable across pass boundaries instead of
+/// resetting each pass.
+fn write_pattern_with_progress(
+    file: &mut File,
+    size: u64,
+    pattern: PatternType,
+    pass_index: u8,
+    total_passes: u8,
+    progress: &Sender<EraseProgress>,
+    cancel: &CancellationToken,
+    start: &Instant,
+    job_bytes_done: &mut u64,
+    job_total_bytes: u64,
+) -> Result<(), EraseError> {
+    const BUFFER_SIZE: usize = 4 * 1024 * 1024;
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+
+    match pattern {
+        PatternType::Zeros => {},
+        PatternType::Ones => buffer.fill(0xFF),
+        PatternType::Random => rand::thread_rng().fill_bytes(&mut buffer),
+        PatternType::Fixed(p) => {
+            for (i, byte) in buffer.iter_mut().enumerate() {
+                *byte = p[i % 3];
+            }
+        },
+    }
+
+    let mut bytes_written = 0u64;
+    while bytes_written < size {
+        if cancel.is_cancelled() {
+            file.sync_all().ok();
+            return Err(EraseError::Cancelled);
+        }
+
+        let to_write = std::cmp::min(BUFFER_SIZE as u64, size - bytes_written) as usize;
+        if matches!(pattern, PatternType::Random) {
+            rand::thread_rng().fill_bytes(&mut buffer[..to_write]);
+        }
+
+        file.write_all(&buffer[..to_write])
+            .map_err(|e| EraseError::Io(format!("Write failed: {}", e)))?;
+
+        bytes_written += to_write as u64;
+        *job_bytes_done += to_write as u64;
+
+        let elapsed_secs = start.elapsed().as_secs_f64();
+        let throughput_mbps = if elapsed_secs > 0.0 {
+            (*job_bytes_done as f64 / elapsed_secs) / (1024.0 * 1024.0)
+        } else {
+            0.0
+        };
+        let eta_seconds = if throughput_mbps > 0.0 {
+            let remaining = job_total_bytes.saturating_sub(*job_bytes_done) as f64;
+            (remaining / (throughput_mbps * 1024.0 * 1024.0)) as u64
+        } else {
+            0
+        };
+
+        let _ = progress.send(EraseProgress {
+            pass_index,
+            total_passes,
+            bytes_written_this_pass: bytes_written,
+            total_size: size,
+            throughput_mbps,
+            eta_seconds,
+        });
+    }
+
     Ok(())
 }
 
+/// Read a just-written pass back in the same 4MB chunks `write_pattern`
+/// uses and confirm every byte matches. Random passes can't be verified
+/// this way (nothing was recorded to compare against) so callers should
+/// skip them. Returns an error naming the first mismatching offset.
+fn verify_pattern(file: &mut File, size: u64, pattern: PatternType) -> Result<(), String> {
+    const BUFFER_SIZE: usize = 4 * 1024 * 1024;
+
+    let mut expected = vec![0u8; BUFFER_SIZE];
+    match pattern {
+        PatternType::Zeros => {},
+        PatternType::Ones => expected.fill(0xFF),
+        PatternType::Fixed(p) => {
+            for (i, byte) in expected.iter_mut().enumerate() {
+                *byte = p[i % 3];
+            }
+        },
+        PatternType::Random => return Ok(()),
+    }
+
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    let mut offset = 0u64;
+
+    while offset < size {
+        let to_read = std::cmp::min(BUFFER_SIZE as u64, size - offset) as usize;
+        file.read_exact(&mut buffer[..to_read])
+            .map_err(|e| format!("Verification read failed at offset {}: {}", offset, e))?;
+
+        if let Some(mismatch) = buffer[..to_read].iter().zip(&expected[..to_read]).position(|(a, b)| a != b) {
+            return Err(format!("Verification mismatch at offset {}", offset + mismatch as u64));
+        }
+
+        offset += to_read as u64;
+    }
+
+    Ok(())
+}
+
+/// Number of random passes shred spreads through every schedule: one at the
+/// start, one at the end, and one through the middle.
+const SHRED_RANDOM_SLOTS: usize = 3;
+
+/// The 22 fixed, non-random patterns shred rotates through: the 16
+/// single-byte fills (0x00, 0x11, .., 0xFF - including 0x55 and 0xAA) plus
+/// the two 3-byte cycling patterns (0x92 0x49 0x24 and 0x6D 0xB6 0xDB) in
+/// each of their three rotations.
+fn shred_deterministic_patterns() -> Vec<PatternType> {
+    let mut patterns = Vec::with_capacity(22);
+
+    for i in 0..16u8 {
+        let b = i * 0x11;
+        patterns.push(PatternType::Fixed([b, b, b]));
+    }
+
+    patterns.push(PatternType::Fixed([0x92, 0x49, 0x24]));
+    patterns.push(PatternType::Fixed([0x49, 0x24, 0x92]));
+    patterns.push(PatternType::Fixed([0x24, 0x92, 0x49]));
+    patterns.push(PatternType::Fixed([0x6D, 0xB6, 0xDB]));
+    patterns.push(PatternType::Fixed([0xB6, 0xDB, 0x6D]));
+    patterns.push(PatternType::Fixed([0xDB, 0x6D, 0xB6]));
+
+    patterns
+}
+
+/// Build a shred-style pass schedule: a shuffled subset (or repeated full
+/// set, if `passes` exceeds the 22 deterministic patterns) interleaved with
+/// [`SHRED_RANDOM_SLOTS`] random passes bookending the start and end, with
+/// any remaining random slots spread through the middle.
+fn build_shred_schedule(passes: u8) -> Vec<PatternType> {
+    let total = passes.max(1) as usize;
+    let mut rng = rand::thread_rng();
+
+    let random_slots = SHRED_RANDOM_SLOTS.min(total);
+    let deterministic_needed = total - random_slots;
+
+    let mut deterministic = Vec::with_capacity(deterministic_needed);
+    while deterministic.len() < deterministic_needed {
+        let mut batch = shred_deterministic_patterns();
+        batch.shuffle(&mut rng);
+        deterministic.extend(batch);
+    }
+    deterministic.truncate(deterministic_needed);
+
+    let mut random_positions = std::collections::HashSet::new();
+    if random_slots >= 1 {
+        random_positions.insert(0);
+    }
+    if random_slots >= 2 {
+        random_positions.insert(total - 1);
+    }
+    if random_slots >= 3 {
+        random_positions.insert(total / 2);
+    }
+    let mut remaining_random = random_slots.saturating_sub(random_positions.len());
+
+    let mut schedule: Vec<Option<PatternType>> = vec![None; total];
+    for &pos in &random_positions {
+        schedule[pos] = Some(PatternType::Random);
+    }
+    if remaining_random > 0 {
+        for slot in schedule.iter_mut() {
+            if remaining_random == 0 {
+                break;
+            }
+            if slot.is_none() {
+                *slot = Some(PatternType::Random);
+                remaining_random -= 1;
+            }
+        }
+    }
+
+    let mut deterministic_iter = deterministic.into_iter();
+    schedule
+        .into_iter()
+        .map(|slot| slot.unwrap_or_else(|| deterministic_iter.next().unwrap_or(PatternType::Random)))
+        .collect()
+}
+
 fn get_gutmann_pattern(pass: u8) -> PatternType {
     // Gutmann method patterns
     match pass {
@@ -186,32 +1133,22 @@ fn get_gutmann_pattern(pass: u8) -> PatternType {
     }
 }
 
-fn get_device_size(device: &str) -> Result<u64, String> {
+pub(crate) fn get_device_size(device: &str) -> Result<u64, String> {
     #[cfg(target_os = "linux")]
     {
-        use std::process::Command;
-        
-        let output = Command::new("blockdev")
-            .args(["--getsize64", device])
-            .output()
-            .map_err(|e| format!("Failed to get device size: {}", e))?;
-        
-        let size_str = String::from_utf8_lossy(&output.stdout);
-        size_str.trim().parse::<u64>()
-            .map_err(|e| format!("Failed to parse size: {}", e))
+        native_device_size_linux(device)
     }
-    
+
     #[cfg(target_os = "macos")]
     {
-        use std::process::Command;
-        
-        let output = Command::new("diskutil")
-            .args(["info", "-plist", device])
-            .output()
-            .map_err(|e| format!("Failed to get device size: {}", e))?;
-        
-        // Parse plist to get size - simplified
-        Ok(0)
+        // `diskutil info -plist` parsing isn't implemented yet. Fabricating a
+        // 0-byte size here would let secure_erase_drive "succeed" after
+        // writing nothing and still sign an ErasureCertificate claiming the
+        // drive was wiped, so refuse outright instead.
+        Err(format!(
+            "Determining the size of {} is not supported on macOS yet",
+            device
+        ))
     }
     
     #[cfg(target_os = "windows")]
@@ -235,54 +1172,14 @@ fn get_device_size(device: &str) -> Result<u64, String> {
     }
 }
 
-/// Quick format a drive (wipe partition table and first MB)
-pub async fn quick_wipe(device: &str) -> Result<(), String> {
-    #[cfg(unix)]
-    {
-        use std::process::Command;
-        
-        // Wipe partition table signatures
-        Command::new("wipefs")
-            .args(["--all", "--force", device])
-            .output()
-            .map_err(|e| format!("wipefs failed: {}", e))?;
-        
-        // Zero first 1MB
-        Command::new("dd")
-            .args([
-                "if=/dev/zero",
-                &format!("of={}", device),
-                "bs=1M",
-                "count=1",
-                "conv=notrunc",
-            ])
-            .output()
-            .map_err(|e| format!("dd failed: {}", e))?;
-        
-        // Zero last 1MB (backup GPT)
-        let size = get_device_size(device)?;
-        let seek = (size / (1024 * 1024)) - 1;
-        
-        Command::new("dd")
-            .args([
-                "if=/dev/zero",
-                &format!("of={}", device),
-                "bs=1M",
-                "count=1",
-                &format!("seek={}", seek),
-                "conv=notrunc",
-            ])
-            .output()
-            .map_err(|e| format!("dd failed: {}", e))?;
-        
-        Ok(())
-    }
-    
-    #[cfg(windows)]
-    {
-        // Windows implementation using diskpart or PowerShell
-        Ok(())
-    }
+/// Quick format a drive: wipe its GPT/MBR structures natively (see
+/// [`crate::partitioning::wipe_partition_tables`]) so neither the kernel nor
+/// other tools see a stale partition layout. No external `wipefs`/`dd`
+/// dependency. Off Linux, [`preflight_check`] refuses the device unless
+/// `force` is set.
+pub async fn quick_wipe(device: &str, force: bool) -> Result<(), String> {
+    preflight_check(device, force)?;
+    crate::partitioning::wipe_partition_tables(device)
 }
 
 #[cfg(test)]
@@ -295,9 +1192,51 @@ mod tests {
             let pattern = get_gutmann_pattern(pass);
             // Just verify we get a pattern for each pass
             match pattern {
-                PatternType::Zeros | PatternType::Ones | 
+                PatternType::Zeros | PatternType::Ones |
                 PatternType::Random | PatternType::Fixed(_) => {}
             }
         }
     }
+
+    #[test]
+    fn test_nist_category() {
+        assert_eq!(SecureEraseMethod::Zeros.nist_category(), SanitizationCategory::Clear);
+        assert_eq!(SecureEraseMethod::Gutmann.nist_category(), SanitizationCategory::Clear);
+        assert_eq!(SecureEraseMethod::HardwareSanitize.nist_category(), SanitizationCategory::Purge);
+    }
+
+    fn sample_certificate() -> ErasureCertificate {
+        ErasureCertificate {
+            device: "/dev/sdx".to_string(),
+            model: "Test Drive".to_string(),
+            serial: "TD12345".to_string(),
+            size_bytes: 1024,
+            method: SecureEraseMethod::Zeros.name().to_string(),
+            nist_category: SanitizationCategory::Clear,
+            passes: vec![PassRecord { pass_index: 0, pattern: "zeros".to_string(), verified: None }],
+            started_at_unix: 0,
+            completed_at_unix: 1,
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_sign_and_verify_certificate_roundtrip() {
+        let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        let signed = sign_certificate(&sample_certificate(), &signing_key).unwrap();
+
+        assert!(signed.signature.is_some());
+        assert!(verify_certificate(&signed).unwrap());
+    }
+
+    #[test]
+    fn test_verify_detects_tampered_certificate() {
+        let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        let mut signed = sign_certificate(&sample_certificate(), &signing_key).unwrap();
+
+        signed.size_bytes = 9999;
+
+        assert!(!verify_certificate(&signed).unwrap());
+    }
 }
@@ -0,0 +1,331 @@
+//! Support for image sets distributed as numbered split parts
+//! (`image.img.001`/`.002`/... or `image.part1`/`.part2`/...) so the rest of
+//! the burner can treat them as a single logical image. [`detect_split_set`]
+//! recognizes the set from its first part's name alone, and [`SplitReader`]
+//! concatenates the parts into one `Read` stream - mirroring nod-rs's
+//! `io/split` handling - so the writer and hasher never need to know a
+//! "file" here is actually several.
+
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// One file backing a split image set, in the order it's read.
+#[derive(Debug, Clone)]
+pub struct SplitPart {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// Find the byte offset where `file_name`'s trailing run of ASCII digits
+/// begins, walking back from the end by whole characters so a multi-byte
+/// character right before the digits (`caf\u{e9}001`) doesn't leave the
+/// split point in the middle of it.
+pub fn trailing_digit_start(file_name: &str) -> usize {
+    file_name
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| !c.is_ascii_digit())
+        .map(|(i, c)| i + c.len_utf8())
+        .unwrap_or(0)
+}
+
+/// If `prefix` ends in the standalone word `part` (case-insensitive) -
+/// either the whole string or preceded by a separator, so `rampart` doesn't
+/// count - return the byte offset where that word starts; otherwise `None`.
+pub fn part_word_end(prefix: &str) -> Option<usize> {
+    let cut = prefix.len().checked_sub(4)?;
+    let word = prefix.get(cut..)?;
+    if !word.eq_ignore_ascii_case("part") {
+        return None;
+    }
+    match prefix[..cut].chars().next_back() {
+        None => Some(cut),
+        Some(c) if matches!(c, '.' | '-' | '_') => Some(cut),
+        Some(_) => None,
+    }
+}
+
+/// If `path` names the first part of a numbered split set, enumerate every
+/// sibling part in order and return them; otherwise `None`. A part is
+/// recognized by a run of ASCII digits at the end of the file name,
+/// immediately preceded by either a separator (a zero-padded suffix
+/// appended after the real extension, `image.img.001`, `.002`, ...) or the
+/// word `part` (replacing the extension instead, `image.part1`,
+/// `.part2`, ...). Requiring one of those two markers - rather than
+/// accepting any trailing digit run - keeps an ordinary incrementing file
+/// name like `disk_backup1`/`disk_backup2` from being mistaken for a split
+/// set. A lone file whose name happens to end in a digit isn't a split set
+/// on its own either - there has to be at least one sibling numbered one
+/// higher, and `path` itself must be the lowest-numbered one - a sibling
+/// numbered one lower ending the same way means `path` is actually the
+/// *middle* of a set, and treating it as the start would burn or hash a
+/// silently truncated image.
+pub fn detect_split_set(path: &Path) -> Option<Vec<SplitPart>> {
+    let file_name = path.file_name()?.to_str()?;
+    let digit_start = trailing_digit_start(file_name);
+    if digit_start == file_name.len() {
+        return None; // no trailing digits at all
+    }
+
+    let prefix = &file_name[..digit_start];
+    if !prefix.ends_with(['.', '-', '_']) && part_word_end(prefix).is_none() {
+        return None; // digits aren't marked as a split suffix by either convention
+    }
+
+    let digit_width = file_name.len() - digit_start;
+    let first_number: u64 = file_name[digit_start..].parse().ok()?;
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+    // `path` must be the *lowest*-numbered part, not just one above a gap -
+    // otherwise picking a part past a missing one would silently drop every
+    // part before it from the logical image.
+    let mut n = first_number;
+    while let Some(previous_number) = n.checked_sub(1) {
+        let previous = dir.join(format!("{prefix}{previous_number:0digit_width$}"));
+        if previous.is_file() {
+            return None; // `path` isn't the first part of its set
+        }
+        n = previous_number;
+    }
+
+    let mut parts = Vec::new();
+    let mut n = first_number;
+    loop {
+        let candidate = dir.join(format!("{prefix}{n:0digit_width$}"));
+        match fs::metadata(&candidate) {
+            Ok(metadata) if metadata.is_file() => {
+                parts.push(SplitPart { path: candidate, size: metadata.len() });
+                n += 1;
+            }
+            _ => break,
+        }
+    }
+
+    if parts.len() < 2 {
+        None
+    } else {
+        Some(parts)
+    }
+}
+
+/// Sum of every part's size - the logical size of the whole set.
+pub fn total_size(parts: &[SplitPart]) -> u64 {
+    parts.iter().map(|p| p.size).sum()
+}
+
+/// A `Read` stream that concatenates a split image set's parts in order,
+/// transparently opening the next file once the current one is exhausted.
+pub struct SplitReader {
+    remaining: std::vec::IntoIter<SplitPart>,
+    current: Option<File>,
+}
+
+impl SplitReader {
+    pub fn open(parts: Vec<SplitPart>) -> io::Result<Self> {
+        let mut remaining = parts.into_iter();
+        let current = remaining.next().map(|part| File::open(&part.path)).transpose()?;
+        Ok(Self { remaining, current })
+    }
+}
+
+impl Read for SplitReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let Some(file) = self.current.as_mut() else {
+                return Ok(0);
+            };
+
+            let n = file.read(buf)?;
+            if n > 0 {
+                return Ok(n);
+            }
+
+            // This part is exhausted; fall through to the next one.
+            self.current = match self.remaining.next() {
+                Some(part) => Some(File::open(&part.path)?),
+                None => None,
+            };
+        }
+    }
+}
+
+/// Open `path` for reading the way the burner does: if it names the first
+/// part of a split set, concatenate every part behind one [`SplitReader`]
+/// and report their summed size; otherwise just open the single file.
+/// Either way the caller gets one `Read` stream and one logical size.
+pub fn open_image(path: &str) -> Result<(Box<dyn Read>, u64), String> {
+    let path = Path::new(path);
+
+    if let Some(parts) = detect_split_set(path) {
+        let size = total_size(&parts);
+        let reader = SplitReader::open(parts)
+            .map_err(|e| format!("Failed to open split image part: {}", e))?;
+        Ok((Box::new(reader), size))
+    } else {
+        let file = File::open(path).map_err(|e| format!("Failed to open image: {}", e))?;
+        let size = file
+            .metadata()
+            .map_err(|e| format!("Failed to read image metadata: {}", e))?
+            .len();
+        Ok((Box::new(file), size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write as _;
+
+    fn write_part(dir: &Path, name: &str, contents: &[u8]) -> PathBuf {
+        let path = dir.join(name);
+        let mut f = File::create(&path).unwrap();
+        f.write_all(contents).unwrap();
+        path
+    }
+
+    fn scratch_dir(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("n01d-forge-split-test-{}-{}", std::process::id(), label));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_detect_numeric_suffix_split_set() {
+        let dir = scratch_dir("numeric");
+        write_part(&dir, "image.img.001", b"part one");
+        write_part(&dir, "image.img.002", b"part two!");
+
+        let parts = detect_split_set(&dir.join("image.img.001")).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].path.file_name().unwrap(), "image.img.001");
+        assert_eq!(parts[1].path.file_name().unwrap(), "image.img.002");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_detect_partn_suffix_split_set() {
+        let dir = scratch_dir("partn");
+        write_part(&dir, "image.part1", b"hello");
+        write_part(&dir, "image.part2", b"world!");
+
+        let parts = detect_split_set(&dir.join("image.part1")).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(total_size(&parts), 11);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_lone_numbered_file_is_not_a_split_set() {
+        let dir = scratch_dir("lone");
+        write_part(&dir, "image.img.001", b"only part");
+
+        assert!(detect_split_set(&dir.join("image.img.001")).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_name_merely_containing_part_is_not_a_split_set() {
+        let dir = scratch_dir("rampart");
+        write_part(&dir, "rampart1", b"one");
+        write_part(&dir, "rampart2", b"two");
+
+        assert!(detect_split_set(&dir.join("rampart1")).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unseparated_incrementing_names_are_not_a_split_set() {
+        let dir = scratch_dir("unseparated");
+        write_part(&dir, "disk_backup1", b"one");
+        write_part(&dir, "disk_backup2", b"two");
+
+        assert!(detect_split_set(&dir.join("disk_backup1")).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_detect_split_set_rejects_a_middle_part() {
+        let dir = scratch_dir("middle");
+        write_part(&dir, "image.img.001", b"one");
+        write_part(&dir, "image.img.002", b"two");
+        write_part(&dir, "image.img.003", b"three");
+
+        assert!(detect_split_set(&dir.join("image.img.002")).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_detect_split_set_rejects_a_part_past_a_gap() {
+        let dir = scratch_dir("gap-middle");
+        write_part(&dir, "image.img.001", b"one");
+        write_part(&dir, "image.img.003", b"three");
+        write_part(&dir, "image.img.004", b"four");
+
+        // image.img.002 is missing, so image.img.003 isn't the true start of
+        // the set even though its immediate predecessor is absent.
+        assert!(detect_split_set(&dir.join("image.img.003")).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_detect_split_set_handles_multibyte_char_before_digits() {
+        let dir = scratch_dir("multibyte");
+        write_part(&dir, "café.001", b"foo");
+        write_part(&dir, "café.002", b"bar");
+
+        let parts = detect_split_set(&dir.join("café.001")).unwrap();
+        assert_eq!(parts.len(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_detect_split_set_stops_at_first_gap() {
+        let dir = scratch_dir("gap");
+        write_part(&dir, "image.img.001", b"one");
+        write_part(&dir, "image.img.002", b"two");
+        write_part(&dir, "image.img.004", b"four");
+
+        let parts = detect_split_set(&dir.join("image.img.001")).unwrap();
+        assert_eq!(parts.len(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_split_reader_concatenates_parts_in_order() {
+        let dir = scratch_dir("concat");
+        write_part(&dir, "image.part1", b"foo");
+        write_part(&dir, "image.part2", b"bar");
+
+        let parts = detect_split_set(&dir.join("image.part1")).unwrap();
+        let mut reader = SplitReader::open(parts).unwrap();
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"foobar");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_open_image_falls_back_to_single_file() {
+        let dir = scratch_dir("single");
+        let path = write_part(&dir, "image.iso", b"not split");
+
+        let (mut reader, size) = open_image(path.to_str().unwrap()).unwrap();
+        assert_eq!(size, 9);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"not split");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
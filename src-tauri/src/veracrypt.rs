@@ -0,0 +1,221 @@
+//! Spec-compliant VeraCrypt volume header creation
+//!
+//! Writes a real VeraCrypt-format volume header so the resulting container
+//! mounts with `veracrypt`/`cryptsetup --type veracrypt`. Layout of the first
+//! 512 bytes of the volume:
+//!
+//! ```text
+//! offset   size  field
+//! 0        64    salt (plaintext, also used as the PBKDF2 salt)
+//! 64       448   header, XTS-encrypted under the header key derived below
+//! ```
+//!
+//! The decrypted 448-byte header is, in turn:
+//!
+//! ```text
+//! local offset  size  field
+//! 0             4     magic "VERA"
+//! 4             2     header format version
+//! 6             2     minimum program version required to open
+//! 8             4     CRC-32 of the 256-byte master key area (local 192..448)
+//! 12            8     volume creation time
+//! 20            8     header modification time
+//! 28            8     hidden volume size
+//! 36            8     volume size
+//! 44            8     encrypted area start offset
+//! 52            8     encrypted area length
+//! 60            4     flags
+//! 64            4     sector size
+//! 68            120   reserved
+//! 188           4     CRC-32 of header bytes 0..=187
+//! 192           256   master key area (concatenated primary/secondary XTS keys)
+//! ```
+//!
+//! A backup copy of the header (same metadata and master keys, including
+//! the encrypted area offset) is written at the end of the volume.
+
+use aes::Aes256;
+use aes::cipher::KeyInit;
+use crc32fast::Hasher as Crc32;
+use hmac::Hmac;
+use pbkdf2::pbkdf2_hmac;
+use rand::RngCore;
+use sha2::{Sha256, Sha512};
+use whirlpool::Whirlpool;
+use xts_mode::{get_tweak_default, Xts128};
+
+use crate::encryption::{veracrypt_hashes, VeraCryptHeader};
+
+pub const SALT_SIZE: usize = 64;
+pub const HEADER_SIZE: usize = 448;
+pub const TOTAL_HEADER_SIZE: usize = SALT_SIZE + HEADER_SIZE;
+pub const MASTER_KEY_AREA_SIZE: usize = 256;
+const MAGIC: &[u8; 4] = b"VERA";
+
+/// Standard VeraCrypt PBKDF2 iteration counts per hash (personal-volume defaults).
+fn default_iterations(hash_id: u32) -> u32 {
+    match hash_id {
+        h if h == veracrypt_hashes::SHA512 => 500_000,
+        h if h == veracrypt_hashes::WHIRLPOOL => 500_000,
+        h if h == veracrypt_hashes::SHA256 => 500_000,
+        _ => 500_000,
+    }
+}
+
+/// Derive the 64-byte header key (two concatenated AES-256 XTS keys) from the
+/// passphrase and salt via PBKDF2-HMAC, using the hash selected by `hash_id`
+/// (one of the [`veracrypt_hashes`] constants).
+pub fn derive_header_key(password: &str, salt: &[u8; SALT_SIZE], hash_id: u32, iterations: u32) -> [u8; 64] {
+    let mut key = [0u8; 64];
+    if hash_id == veracrypt_hashes::SHA256 {
+        pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, iterations, &mut key);
+    } else if hash_id == veracrypt_hashes::WHIRLPOOL {
+        pbkdf2_hmac::<Whirlpool>(password.as_bytes(), salt, iterations, &mut key);
+    } else {
+        // SHA-512 is VeraCrypt's default PRF.
+        pbkdf2_hmac::<Sha512>(password.as_bytes(), salt, iterations, &mut key);
+    }
+    key
+}
+
+fn xts_for(header_key: &[u8; 64]) -> Xts128<Aes256> {
+    let cipher_1 = Aes256::new_from_slice(&header_key[..32]).expect("32-byte AES-256 key");
+    let cipher_2 = Aes256::new_from_slice(&header_key[32..]).expect("32-byte AES-256 key");
+    Xts128::new(cipher_1, cipher_2)
+}
+
+/// Build the 448-byte plaintext header block (before XTS encryption).
+fn build_plaintext_header(meta: &VeraCryptHeader, master_key_area: &[u8; MASTER_KEY_AREA_SIZE]) -> [u8; HEADER_SIZE] {
+    let mut buf = [0u8; HEADER_SIZE];
+
+    buf[0..4].copy_from_slice(MAGIC);
+    buf[4..6].copy_from_slice(&meta.version.to_be_bytes());
+    buf[6..8].copy_from_slice(&meta.required_program_version.to_be_bytes());
+
+    let mut key_crc = Crc32::new();
+    key_crc.update(master_key_area);
+    buf[8..12].copy_from_slice(&key_crc.finalize().to_be_bytes());
+
+    buf[12..20].copy_from_slice(&meta.volume_creation_time.to_be_bytes());
+    buf[20..28].copy_from_slice(&meta.header_creation_time.to_be_bytes());
+    buf[28..36].copy_from_slice(&meta.hidden_volume_size.to_be_bytes());
+    buf[36..44].copy_from_slice(&meta.volume_size.to_be_bytes());
+    buf[44..52].copy_from_slice(&meta.encrypted_area_start.to_be_bytes());
+    buf[52..60].copy_from_slice(&meta.encrypted_area_length.to_be_bytes());
+    buf[60..64].copy_from_slice(&meta.flags.to_be_bytes());
+    buf[64..68].copy_from_slice(&meta.sector_size.to_be_bytes());
+    // 68..188 reserved, left zeroed
+
+    let mut header_crc = Crc32::new();
+    header_crc.update(&buf[0..188]);
+    buf[188..192].copy_from_slice(&header_crc.finalize().to_be_bytes());
+
+    buf[192..448].copy_from_slice(master_key_area);
+
+    buf
+}
+
+/// Encrypt the 448-byte plaintext header in XTS mode under the header key,
+/// using data-unit index 0 as VeraCrypt does for the header itself.
+fn encrypt_header(plaintext: &[u8; HEADER_SIZE], header_key: &[u8; 64]) -> [u8; HEADER_SIZE] {
+    let mut data = *plaintext;
+    xts_for(header_key).encrypt_area(&mut data, HEADER_SIZE, 0, get_tweak_default);
+    data
+}
+
+/// Decrypt a 448-byte header region previously produced by [`encrypt_header`].
+pub fn decrypt_header(ciphertext: &[u8; HEADER_SIZE], header_key: &[u8; 64]) -> [u8; HEADER_SIZE] {
+    let mut data = *ciphertext;
+    xts_for(header_key).decrypt_area(&mut data, HEADER_SIZE, 0, get_tweak_default);
+    data
+}
+
+/// Fully assembled, ready-to-write 512-byte volume header (salt + encrypted
+/// region), plus the plaintext master key area that produced it so the
+/// caller can reuse the same keys when writing the backup header copy.
+pub struct EncodedHeader {
+    pub bytes: [u8; TOTAL_HEADER_SIZE],
+    pub master_key_area: [u8; MASTER_KEY_AREA_SIZE],
+}
+
+/// Build a complete VeraCrypt volume header: a fresh random salt, a freshly
+/// generated master key area, PBKDF2-derived header key, and the XTS
+/// encrypted 448-byte region, ready to be written at the start (and, as a
+/// backup copy, near the end) of the volume.
+pub fn create_header(password: &str, meta: &VeraCryptHeader) -> EncodedHeader {
+    let mut salt = [0u8; SALT_SIZE];
+    rand::thread_rng().fill_bytes(&mut salt);
+
+    let mut master_key_area = [0u8; MASTER_KEY_AREA_SIZE];
+    rand::thread_rng().fill_bytes(&mut master_key_area);
+
+    let iterations = default_iterations(meta.hash_algorithm);
+    let header_key = derive_header_key(password, &salt, meta.hash_algorithm, iterations);
+
+    let plaintext = build_plaintext_header(meta, &master_key_area);
+    let encrypted = encrypt_header(&plaintext, &header_key);
+
+    let mut bytes = [0u8; TOTAL_HEADER_SIZE];
+    bytes[..SALT_SIZE].copy_from_slice(&salt);
+    bytes[SALT_SIZE..].copy_from_slice(&encrypted);
+
+    EncodedHeader { bytes, master_key_area }
+}
+
+/// Build the backup header written at the end of the volume. VeraCrypt
+/// re-derives a fresh salt for the backup copy but reuses the same metadata
+/// and master keys so either header can bring the volume up.
+pub fn create_backup_header(password: &str, meta: &VeraCryptHeader, master_key_area: &[u8; MASTER_KEY_AREA_SIZE]) -> EncodedHeader {
+    let mut salt = [0u8; SALT_SIZE];
+    rand::thread_rng().fill_bytes(&mut salt);
+
+    let iterations = default_iterations(meta.hash_algorithm);
+    let header_key = derive_header_key(password, &salt, meta.hash_algorithm, iterations);
+
+    let plaintext = build_plaintext_header(meta, master_key_area);
+    let encrypted = encrypt_header(&plaintext, &header_key);
+
+    let mut bytes = [0u8; TOTAL_HEADER_SIZE];
+    bytes[..SALT_SIZE].copy_from_slice(&salt);
+    bytes[SALT_SIZE..].copy_from_slice(&encrypted);
+
+    EncodedHeader { bytes, master_key_area: *master_key_area }
+}
+
+type _HmacSha512 = Hmac<Sha512>; // keeps `hmac` in the dependency graph explicit for the PRFs above
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encryption::veracrypt_algorithms;
+
+    #[test]
+    fn test_header_roundtrips_through_xts() {
+        let meta = VeraCryptHeader::new(10 * 1024 * 1024, veracrypt_algorithms::AES, veracrypt_hashes::SHA256);
+        let encoded = create_header("correct horse battery staple", &meta);
+
+        let salt: [u8; SALT_SIZE] = encoded.bytes[..SALT_SIZE].try_into().unwrap();
+        let encrypted: [u8; HEADER_SIZE] = encoded.bytes[SALT_SIZE..].try_into().unwrap();
+
+        let iterations = default_iterations(meta.hash_algorithm);
+        let header_key = derive_header_key("correct horse battery staple", &salt, meta.hash_algorithm, iterations);
+        let decrypted = decrypt_header(&encrypted, &header_key);
+
+        assert_eq!(&decrypted[0..4], MAGIC);
+    }
+
+    #[test]
+    fn test_wrong_password_does_not_decode_magic() {
+        let meta = VeraCryptHeader::new(10 * 1024 * 1024, veracrypt_algorithms::AES, veracrypt_hashes::SHA256);
+        let encoded = create_header("correct horse battery staple", &meta);
+
+        let salt: [u8; SALT_SIZE] = encoded.bytes[..SALT_SIZE].try_into().unwrap();
+        let encrypted: [u8; HEADER_SIZE] = encoded.bytes[SALT_SIZE..].try_into().unwrap();
+
+        let iterations = default_iterations(meta.hash_algorithm);
+        let wrong_key = derive_header_key("wrong password", &salt, meta.hash_algorithm, iterations);
+        let decrypted = decrypt_header(&encrypted, &wrong_key);
+
+        assert_ne!(&decrypted[0..4], MAGIC);
+    }
+}
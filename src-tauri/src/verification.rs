@@ -0,0 +1,292 @@
+//! Post-burn verification helpers
+//!
+//! Sidecar-file discovery for pre-burn source verification (`<image>.sha256`,
+//! `<image>.md5`), the rolling hasher used for a single-algorithm read-back,
+//! and a parallel multi-digest subsystem (modeled on nod-rs's `digest_thread`)
+//! that streams a source exactly once and feeds every chunk to one hasher
+//! thread per requested algorithm, so CRC32/MD5/SHA1/SHA256/SHA512 can all be
+//! computed concurrently from a single read.
+
+use crc32fast::Hasher as Crc32Hasher;
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::sync_channel;
+use std::sync::Arc;
+use std::thread;
+
+/// A digest recovered from a `<image>.sha256` / `<image>.md5` sidecar file.
+#[derive(Debug, Clone)]
+pub struct SidecarHash {
+    pub algorithm: String,
+    pub expected: String,
+}
+
+/// Look for a sidecar checksum file next to `image_path` and parse its digest.
+/// Checks `.sha256` before `.md5`, matching the order most mirrors publish them in.
+pub fn find_sidecar_hash(image_path: &str) -> Option<SidecarHash> {
+    for (ext, algorithm) in [("sha256", "sha256"), ("md5", "md5")] {
+        let sidecar: PathBuf = format!("{}.{}", image_path, ext).into();
+        if let Ok(contents) = std::fs::read_to_string(&sidecar) {
+            if let Some(expected) = parse_sidecar_digest(&contents) {
+                return Some(SidecarHash {
+                    algorithm: algorithm.to_string(),
+                    expected,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Extract the hex digest from a sidecar file's contents. Accepts both the
+/// common `<hash>  <filename>` coreutils format and a bare hash on its own.
+fn parse_sidecar_digest(contents: &str) -> Option<String> {
+    contents
+        .split_whitespace()
+        .next()
+        .filter(|tok| !tok.is_empty() && tok.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(|tok| tok.to_lowercase())
+}
+
+/// Rolling hasher abstraction shared by the write and read-back verification
+/// passes so either CRC32 (fast) or SHA-256 can be selected per burn.
+pub enum RollingDigest {
+    Crc32(Crc32Hasher),
+    Sha256(Box<Sha256>),
+}
+
+impl RollingDigest {
+    pub fn new(algorithm: &str) -> Result<Self, String> {
+        match algorithm.to_lowercase().as_str() {
+            "crc32" => Ok(RollingDigest::Crc32(Crc32Hasher::new())),
+            "sha256" => Ok(RollingDigest::Sha256(Box::new(Sha256::new()))),
+            other => Err(format!("Unsupported verification algorithm: {}", other)),
+        }
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        match self {
+            RollingDigest::Crc32(h) => h.update(chunk),
+            RollingDigest::Sha256(h) => h.update(chunk),
+        }
+    }
+
+    pub fn finalize_hex(self) -> String {
+        match self {
+            RollingDigest::Crc32(h) => format!("{:08x}", h.finalize()),
+            RollingDigest::Sha256(h) => hex::encode(h.finalize()),
+        }
+    }
+}
+
+/// One of the digest algorithms the parallel hashing subsystem below can
+/// compute in the same pass as every other requested algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigestAlgorithm {
+    Crc32,
+    Md5,
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    pub fn parse(algorithm: &str) -> Result<Self, String> {
+        match algorithm.to_lowercase().as_str() {
+            "crc32" => Ok(DigestAlgorithm::Crc32),
+            "md5" => Ok(DigestAlgorithm::Md5),
+            "sha1" => Ok(DigestAlgorithm::Sha1),
+            "sha256" => Ok(DigestAlgorithm::Sha256),
+            "sha512" => Ok(DigestAlgorithm::Sha512),
+            other => Err(format!("Unsupported algorithm: {}", other)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Crc32 => "crc32",
+            DigestAlgorithm::Md5 => "md5",
+            DigestAlgorithm::Sha1 => "sha1",
+            DigestAlgorithm::Sha256 => "sha256",
+            DigestAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// Drain `chunks` to completion and return the finished hex digest. Runs
+    /// on its own worker thread in [`hash_reader_multi`] - each algorithm
+    /// gets an independent hasher so none of them block on the others.
+    fn drain_and_finalize(self, chunks: std::sync::mpsc::Receiver<Arc<[u8]>>) -> String {
+        match self {
+            DigestAlgorithm::Crc32 => {
+                let mut hasher = Crc32Hasher::new();
+                for chunk in chunks {
+                    hasher.update(&chunk);
+                }
+                format!("{:08x}", hasher.finalize())
+            }
+            DigestAlgorithm::Md5 => {
+                let mut hasher = Md5::new();
+                for chunk in chunks {
+                    hasher.update(&chunk);
+                }
+                hex::encode(hasher.finalize())
+            }
+            DigestAlgorithm::Sha1 => {
+                let mut hasher = Sha1::new();
+                for chunk in chunks {
+                    hasher.update(&chunk);
+                }
+                hex::encode(hasher.finalize())
+            }
+            DigestAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                for chunk in chunks {
+                    hasher.update(&chunk);
+                }
+                hex::encode(hasher.finalize())
+            }
+            DigestAlgorithm::Sha512 => {
+                let mut hasher = Sha512::new();
+                for chunk in chunks {
+                    hasher.update(&chunk);
+                }
+                hex::encode(hasher.finalize())
+            }
+        }
+    }
+}
+
+/// How many owned chunks a hasher's channel is allowed to queue up before the
+/// reader thread blocks, bounding memory no matter how much slower one
+/// hasher is than the others.
+const DIGEST_CHANNEL_CAPACITY: usize = 4;
+const DIGEST_BUFFER_SIZE: usize = 4 * 1024 * 1024;
+
+/// Stream `reader` exactly once, feeding every chunk to one dedicated worker
+/// thread per entry in `algorithms` over a bounded `sync_channel`, so every
+/// digest is computed concurrently with the read and with each other instead
+/// of one algorithm per pass. Returns one hex digest per requested algorithm,
+/// in the same order they were requested.
+pub fn hash_reader_multi<R: Read>(
+    mut reader: R,
+    algorithms: &[DigestAlgorithm],
+) -> Result<Vec<(DigestAlgorithm, String)>, String> {
+    let mut senders = Vec::with_capacity(algorithms.len());
+    let mut workers = Vec::with_capacity(algorithms.len());
+
+    for &algorithm in algorithms {
+        let (tx, rx) = sync_channel::<Arc<[u8]>>(DIGEST_CHANNEL_CAPACITY);
+        senders.push(tx);
+        workers.push(thread::spawn(move || algorithm.drain_and_finalize(rx)));
+    }
+
+    let mut buffer = vec![0u8; DIGEST_BUFFER_SIZE];
+    let mut read_error = None;
+
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => {
+                let chunk: Arc<[u8]> = Arc::from(&buffer[..n]);
+                for sender in &senders {
+                    // Only disconnects if that hasher thread panicked, which
+                    // surfaces below when its join() result is collected.
+                    let _ = sender.send(chunk.clone());
+                }
+            }
+            Err(e) => {
+                read_error = Some(format!("Read error: {}", e));
+                break;
+            }
+        }
+    }
+
+    // Drop the senders so every worker's `for chunk in chunks` loop ends.
+    drop(senders);
+
+    let mut results = Vec::with_capacity(workers.len());
+    for (&algorithm, worker) in algorithms.iter().zip(workers) {
+        let hash = worker
+            .join()
+            .map_err(|_| format!("{} hasher thread panicked", algorithm.name()))?;
+        results.push((algorithm, hash));
+    }
+
+    match read_error {
+        Some(e) => Err(e),
+        None => Ok(results),
+    }
+}
+
+/// Whether `path` looks like an image file that could plausibly have a
+/// sidecar checksum sitting next to it (used to avoid needless stat calls).
+pub fn has_sidecar_candidates(path: &Path) -> bool {
+    path.extension().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sidecar_digest_plain() {
+        let hash = "a".repeat(64);
+        assert_eq!(parse_sidecar_digest(&hash), Some(hash));
+    }
+
+    #[test]
+    fn test_parse_sidecar_digest_coreutils_format() {
+        let hash = "b".repeat(64);
+        let line = format!("{}  image.iso\n", hash);
+        assert_eq!(parse_sidecar_digest(&line), Some(hash));
+    }
+
+    #[test]
+    fn test_rolling_digest_crc32_matches_crc32fast() {
+        let mut digest = RollingDigest::new("crc32").unwrap();
+        digest.update(b"hello world");
+        let mut reference = Crc32Hasher::new();
+        reference.update(b"hello world");
+        assert_eq!(digest.finalize_hex(), format!("{:08x}", reference.finalize()));
+    }
+
+    #[test]
+    fn test_hash_reader_multi_matches_individual_hashers() {
+        let data = b"the quick brown fox jumps over the lazy dog".to_vec();
+        let algorithms = [
+            DigestAlgorithm::Crc32,
+            DigestAlgorithm::Md5,
+            DigestAlgorithm::Sha1,
+            DigestAlgorithm::Sha256,
+            DigestAlgorithm::Sha512,
+        ];
+
+        let results = hash_reader_multi(std::io::Cursor::new(data.clone()), &algorithms).unwrap();
+
+        let mut crc32 = Crc32Hasher::new();
+        crc32.update(&data);
+        let mut md5 = Md5::new();
+        md5.update(&data);
+        let mut sha1 = Sha1::new();
+        sha1.update(&data);
+        let mut sha256 = Sha256::new();
+        sha256.update(&data);
+        let mut sha512 = Sha512::new();
+        sha512.update(&data);
+
+        let expected = [
+            format!("{:08x}", crc32.finalize()),
+            hex::encode(md5.finalize()),
+            hex::encode(sha1.finalize()),
+            hex::encode(sha256.finalize()),
+            hex::encode(sha512.finalize()),
+        ];
+
+        for ((_, actual), expected) in results.iter().zip(expected.iter()) {
+            assert_eq!(actual, expected);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Background burn worker
+//!
+//! Streams an image to a target device on its own thread in fixed-size
+//! blocks, reporting progress back to the UI thread over an `mpsc` channel so
+//! `ForgeApp::update` can poll it each frame without blocking the egui loop.
+
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+
+const BLOCK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Messages sent from the burn worker thread back to the UI thread.
+pub enum BurnMessage {
+    Progress { bytes_written: u64, total_bytes: u64 },
+    Status(String),
+    Done(Result<BurnOutcome, String>),
+}
+
+pub struct BurnOutcome {
+    pub bytes_written: u64,
+    /// `Some(true/false)` when verification ran, `None` when it was skipped.
+    pub verified: Option<bool>,
+}
+
+/// Spawn the burn worker and return the receiver the UI polls each frame,
+/// plus the cancel flag the UI can set to request an early stop.
+pub fn start_burn(image_path: String, target_device: String, verify: bool) -> (Receiver<BurnMessage>, Arc<AtomicBool>) {
+    let (tx, rx) = mpsc::channel();
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let worker_cancel = cancel_flag.clone();
+
+    thread::spawn(move || {
+        let result = run_burn(&image_path, &target_device, verify, &worker_cancel, &tx);
+        let _ = tx.send(BurnMessage::Done(result));
+    });
+
+    (rx, cancel_flag)
+}
+
+fn run_burn(
+    image_path: &str,
+    target_device: &str,
+    verify: bool,
+    cancel: &AtomicBool,
+    tx: &Sender<BurnMessage>,
+) -> Result<BurnOutcome, String> {
+    let image_file = File::open(image_path)
+        .map_err(|e| format!("Failed to open image: {}", e))?;
+    let total_bytes = image_file.metadata()
+        .map_err(|e| format!("Failed to read image metadata: {}", e))?
+        .len();
+
+    let target_file = OpenOptions::new()
+        .write(true)
+        .open(target_device)
+        .map_err(|e| format!("Failed to open target drive: {}", e))?;
+
+    let _ = tx.send(BurnMessage::Status("Writing image to drive...".to_string()));
+
+    let mut reader = BufReader::with_capacity(BLOCK_SIZE, image_file);
+    let mut writer = BufWriter::with_capacity(BLOCK_SIZE, target_file);
+    let mut buffer = vec![0u8; BLOCK_SIZE];
+    let mut bytes_written = 0u64;
+    let mut hasher = verify.then(Sha256::new);
+
+    loop {
+        if cancel.load(Ordering::SeqCst) {
+            return Err("Burn cancelled".to_string());
+        }
+
+        let n = reader.read(&mut buffer).map_err(|e| format!("Read error: {}", e))?;
+        if n == 0 {
+            break;
+        }
+
+        writer.write_all(&buffer[..n]).map_err(|e| format!("Write error: {}", e))?;
+        if let Some(h) = hasher.as_mut() {
+            h.update(&buffer[..n]);
+        }
+
+        bytes_written += n as u64;
+        let _ = tx.send(BurnMessage::Progress { bytes_written, total_bytes });
+    }
+
+    writer.flush().map_err(|e| format!("Flush error: {}", e))?;
+    writer.get_ref().sync_all().map_err(|e| format!("Sync error: {}", e))?;
+
+    let verified = if let Some(hasher) = hasher {
+        let _ = tx.send(BurnMessage::Status("Verifying write...".to_string()));
+        let source_hash = hex::encode(hasher.finalize());
+        let written_hash = hash_device_prefix(target_device, bytes_written, cancel, tx)?;
+        Some(source_hash == written_hash)
+    } else {
+        None
+    };
+
+    Ok(BurnOutcome { bytes_written, verified })
+}
+
+/// Re-read the first `len` bytes of `device` and return their SHA-256 digest.
+fn hash_device_prefix(
+    device: &str,
+    len: u64,
+    cancel: &AtomicBool,
+    tx: &Sender<BurnMessage>,
+) -> Result<String, String> {
+    let file = File::open(device).map_err(|e| format!("Failed to reopen target for verify: {}", e))?;
+    let mut reader = BufReader::with_capacity(BLOCK_SIZE, file);
+    let mut buffer = vec![0u8; BLOCK_SIZE];
+    let mut hasher = Sha256::new();
+    let mut bytes_read = 0u64;
+
+    while bytes_read < len {
+        if cancel.load(Ordering::SeqCst) {
+            return Err("Burn cancelled during verification".to_string());
+        }
+
+        let to_read = std::cmp::min(buffer.len() as u64, len - bytes_read) as usize;
+        let n = reader.read(&mut buffer[..to_read]).map_err(|e| format!("Read error: {}", e))?;
+        if n == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..n]);
+        bytes_read += n as u64;
+        let _ = tx.send(BurnMessage::Progress { bytes_written: bytes_read, total_bytes: len });
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
@@ -0,0 +1,93 @@
+//! Guided confirmation modal for destructive writes
+//!
+//! Burning and erasing both overwrite a device irreversibly, so both route
+//! through this modal instead of acting on a single button click: the caller
+//! supplies a checklist of facts the user must tick off, then must type the
+//! exact device path back before the action is allowed to proceed.
+
+use crate::theme::*;
+use eframe::egui::{self, RichText, Vec2};
+
+pub struct ConfirmModal {
+    open: bool,
+    title: String,
+    device: String,
+    typed_device: String,
+    checklist: Vec<(String, bool)>,
+}
+
+impl Default for ConfirmModal {
+    fn default() -> Self {
+        Self {
+            open: false,
+            title: String::new(),
+            device: String::new(),
+            typed_device: String::new(),
+            checklist: Vec::new(),
+        }
+    }
+}
+
+impl ConfirmModal {
+    /// Open the modal for `device`, presenting `checklist_items` as facts the
+    /// user must individually acknowledge before typing the device path.
+    pub fn open_for(&mut self, title: &str, device: &str, checklist_items: &[&str]) {
+        self.open = true;
+        self.title = title.to_string();
+        self.device = device.to_string();
+        self.typed_device.clear();
+        self.checklist = checklist_items.iter().map(|s| (s.to_string(), false)).collect();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn all_checked(&self) -> bool {
+        !self.checklist.is_empty() && self.checklist.iter().all(|(_, checked)| *checked)
+    }
+
+    /// Render the modal if open. Returns `true` the one frame the user
+    /// confirms, after which the modal closes itself.
+    pub fn show(&mut self, ctx: &egui::Context) -> bool {
+        if !self.open {
+            return false;
+        }
+
+        let mut confirmed = false;
+        let mut open = self.open;
+
+        egui::Window::new(self.title.clone())
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .default_size(Vec2::new(420.0, 320.0))
+            .show(ctx, |ui| {
+                ui.label(RichText::new(format!("Target: {}", self.device)).color(WARNING).strong());
+                ui.add_space(12.0);
+
+                for (label, checked) in self.checklist.iter_mut() {
+                    ui.checkbox(checked, label.as_str());
+                }
+
+                ui.add_space(12.0);
+                ui.label(RichText::new(format!("Type \"{}\" to confirm:", self.device)).color(TEXT_DIM).size(12.0));
+                ui.add(egui::TextEdit::singleline(&mut self.typed_device).desired_width(ui.available_width()));
+
+                ui.add_space(16.0);
+
+                let can_confirm = self.all_checked() && self.typed_device == self.device;
+                ui.add_enabled_ui(can_confirm, |ui| {
+                    let btn = egui::Button::new(RichText::new("Confirm and proceed").color(eframe::egui::Color32::WHITE).strong())
+                        .fill(DANGER)
+                        .min_size(Vec2::new(200.0, 36.0));
+                    if ui.add(btn).clicked() {
+                        confirmed = true;
+                    }
+                });
+            });
+
+        self.open = open && !confirmed;
+        confirmed
+    }
+}
@@ -0,0 +1,114 @@
+//! Background LUKS2 format worker
+//!
+//! Shells out to `cryptsetup luksFormat` on its own thread, feeding the
+//! passphrase over stdin rather than argv so it never shows up in `ps`.
+//! Mirrors the worker shape in `burn.rs` and `erase.rs`, minus a progress
+//! channel since cryptsetup doesn't report incremental progress.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Argon2id KDF tuning knobs exposed to the Encrypt tab.
+#[derive(Debug, Clone, Copy)]
+pub struct Argon2Params {
+    pub iterations: u32,
+    pub memory_kib: u32,
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        // cryptsetup's own defaults for --pbkdf argon2id.
+        Self { iterations: 4, memory_kib: 1_048_576, parallelism: 4 }
+    }
+}
+
+pub enum EncryptMessage {
+    Status(String),
+    Done(Result<(), String>),
+}
+
+/// Spawn the format worker and return the receiver the UI polls each frame,
+/// plus the cancel flag the UI can set to request an early stop.
+pub fn start_format(
+    device: String,
+    passphrase: String,
+    params: Argon2Params,
+) -> (Receiver<EncryptMessage>, Arc<AtomicBool>) {
+    let (tx, rx) = mpsc::channel();
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let worker_cancel = cancel_flag.clone();
+
+    thread::spawn(move || {
+        let result = run_format(&device, &passphrase, params, &worker_cancel, &tx);
+        let _ = tx.send(EncryptMessage::Done(result));
+    });
+
+    (rx, cancel_flag)
+}
+
+fn run_format(
+    device: &str,
+    passphrase: &str,
+    params: Argon2Params,
+    cancel: &AtomicBool,
+    tx: &Sender<EncryptMessage>,
+) -> Result<(), String> {
+    let _ = tx.send(EncryptMessage::Status("Formatting LUKS2 volume...".to_string()));
+
+    let mut child = Command::new("cryptsetup")
+        .args([
+            "luksFormat",
+            "--type", "luks2",
+            "--pbkdf", "argon2id",
+            "--pbkdf-force-iterations", &params.iterations.to_string(),
+            "--pbkdf-memory", &params.memory_kib.to_string(),
+            "--pbkdf-parallel", &params.parallelism.to_string(),
+            "--batch-mode",
+            "--key-file", "-",
+            device,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to launch cryptsetup: {}", e))?;
+
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| "Failed to open cryptsetup stdin".to_string())?
+        .write_all(passphrase.as_bytes())
+        .map_err(|e| format!("Failed to write passphrase: {}", e))?;
+
+    loop {
+        if cancel.load(Ordering::SeqCst) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("Encryption setup cancelled".to_string());
+        }
+
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                if status.success() {
+                    let _ = tx.send(EncryptMessage::Status("LUKS2 volume ready".to_string()));
+                    return Ok(());
+                }
+
+                let mut stderr = String::new();
+                if let Some(mut s) = child.stderr.take() {
+                    use std::io::Read;
+                    let _ = s.read_to_string(&mut stderr);
+                }
+                return Err(format!("cryptsetup exited with {}: {}", status, stderr.trim()));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(150)),
+            Err(e) => return Err(format!("Failed to wait on cryptsetup: {}", e)),
+        }
+    }
+}
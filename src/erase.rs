@@ -0,0 +1,224 @@
+//! Background secure-erase worker
+//!
+//! Overwrites a target device on its own thread with the selected pattern
+//! schedule, reporting pass/progress back to the UI thread over an `mpsc`
+//! channel so `ForgeApp::update` can poll it each frame without blocking the
+//! egui loop. Mirrors the worker shape in `burn.rs`.
+
+use rand::RngCore;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+
+const BLOCK_SIZE: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EraseMethod {
+    /// Single pass of zeros
+    Zeros,
+    /// Single pass of random data
+    Random,
+    /// DoD 5220.22-M standard (3 passes: zeros, ones, random)
+    DoD,
+    /// Gutmann method (35 passes)
+    Gutmann,
+}
+
+impl EraseMethod {
+    pub fn passes(&self) -> u8 {
+        match self {
+            EraseMethod::Zeros => 1,
+            EraseMethod::Random => 1,
+            EraseMethod::DoD => 3,
+            EraseMethod::Gutmann => 35,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EraseMethod::Zeros => "Zero Fill",
+            EraseMethod::Random => "Random Fill",
+            EraseMethod::DoD => "DoD 5220.22-M",
+            EraseMethod::Gutmann => "Gutmann (35-pass)",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Pattern {
+    Zeros,
+    Ones,
+    Random,
+    Fixed([u8; 3]),
+}
+
+fn pass_pattern(method: EraseMethod, pass: u8) -> Pattern {
+    match method {
+        EraseMethod::Zeros => Pattern::Zeros,
+        EraseMethod::Random => Pattern::Random,
+        EraseMethod::DoD => match pass {
+            0 => Pattern::Zeros,
+            1 => Pattern::Ones,
+            _ => Pattern::Random,
+        },
+        EraseMethod::Gutmann => gutmann_pattern(pass),
+    }
+}
+
+fn gutmann_pattern(pass: u8) -> Pattern {
+    match pass {
+        0..=3 => Pattern::Random,
+        4 => Pattern::Fixed([0x55, 0x55, 0x55]),
+        5 => Pattern::Fixed([0xAA, 0xAA, 0xAA]),
+        6 => Pattern::Fixed([0x92, 0x49, 0x24]),
+        7 => Pattern::Fixed([0x49, 0x24, 0x92]),
+        8 => Pattern::Fixed([0x24, 0x92, 0x49]),
+        9 => Pattern::Fixed([0x00, 0x00, 0x00]),
+        10 => Pattern::Fixed([0x11, 0x11, 0x11]),
+        11 => Pattern::Fixed([0x22, 0x22, 0x22]),
+        12 => Pattern::Fixed([0x33, 0x33, 0x33]),
+        13 => Pattern::Fixed([0x44, 0x44, 0x44]),
+        14 => Pattern::Fixed([0x55, 0x55, 0x55]),
+        15 => Pattern::Fixed([0x66, 0x66, 0x66]),
+        16 => Pattern::Fixed([0x77, 0x77, 0x77]),
+        17 => Pattern::Fixed([0x88, 0x88, 0x88]),
+        18 => Pattern::Fixed([0x99, 0x99, 0x99]),
+        19 => Pattern::Fixed([0xAA, 0xAA, 0xAA]),
+        20 => Pattern::Fixed([0xBB, 0xBB, 0xBB]),
+        21 => Pattern::Fixed([0xCC, 0xCC, 0xCC]),
+        22 => Pattern::Fixed([0xDD, 0xDD, 0xDD]),
+        23 => Pattern::Fixed([0xEE, 0xEE, 0xEE]),
+        24 => Pattern::Fixed([0xFF, 0xFF, 0xFF]),
+        25 => Pattern::Fixed([0x92, 0x49, 0x24]),
+        26 => Pattern::Fixed([0x49, 0x24, 0x92]),
+        27 => Pattern::Fixed([0x24, 0x92, 0x49]),
+        28 => Pattern::Fixed([0x6D, 0xB6, 0xDB]),
+        29 => Pattern::Fixed([0xB6, 0xDB, 0x6D]),
+        30 => Pattern::Fixed([0xDB, 0x6D, 0xB6]),
+        _ => Pattern::Random,
+    }
+}
+
+/// Messages sent from the erase worker thread back to the UI thread.
+pub enum EraseMessage {
+    Progress { pass: u8, total_passes: u8, bytes_written: u64, total_bytes: u64 },
+    Status(String),
+    Done(Result<(), String>),
+}
+
+/// Spawn the erase worker and return the receiver the UI polls each frame,
+/// plus the cancel flag the UI can set to request an early stop.
+pub fn start_erase(device: String, method: EraseMethod) -> (Receiver<EraseMessage>, Arc<AtomicBool>) {
+    let (tx, rx) = mpsc::channel();
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let worker_cancel = cancel_flag.clone();
+
+    thread::spawn(move || {
+        let result = run_erase(&device, method, &worker_cancel, &tx);
+        let _ = tx.send(EraseMessage::Done(result));
+    });
+
+    (rx, cancel_flag)
+}
+
+fn run_erase(
+    device: &str,
+    method: EraseMethod,
+    cancel: &AtomicBool,
+    tx: &Sender<EraseMessage>,
+) -> Result<(), String> {
+    let size = device_size(device)?;
+    let total_passes = method.passes();
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .open(device)
+        .map_err(|e| format!("Failed to open device: {}", e))?;
+
+    for pass in 0..total_passes {
+        let _ = tx.send(EraseMessage::Status(format!(
+            "{}: pass {}/{}",
+            method.name(),
+            pass + 1,
+            total_passes
+        )));
+
+        file.seek(SeekFrom::Start(0)).map_err(|e| format!("Seek failed: {}", e))?;
+        write_pattern(&mut file, size, pass_pattern(method, pass), pass, total_passes, cancel, tx)?;
+    }
+
+    file.sync_all().map_err(|e| format!("Sync failed: {}", e))?;
+    Ok(())
+}
+
+fn write_pattern(
+    file: &mut File,
+    size: u64,
+    pattern: Pattern,
+    pass: u8,
+    total_passes: u8,
+    cancel: &AtomicBool,
+    tx: &Sender<EraseMessage>,
+) -> Result<(), String> {
+    let mut buffer = vec![0u8; BLOCK_SIZE];
+    match pattern {
+        Pattern::Zeros => {}
+        Pattern::Ones => buffer.fill(0xFF),
+        Pattern::Random => rand::thread_rng().fill_bytes(&mut buffer),
+        Pattern::Fixed(p) => {
+            for (i, byte) in buffer.iter_mut().enumerate() {
+                *byte = p[i % 3];
+            }
+        }
+    }
+
+    let mut bytes_written = 0u64;
+    while bytes_written < size {
+        if cancel.load(Ordering::SeqCst) {
+            return Err("Erase cancelled".to_string());
+        }
+
+        let to_write = std::cmp::min(BLOCK_SIZE as u64, size - bytes_written) as usize;
+        if matches!(pattern, Pattern::Random) {
+            rand::thread_rng().fill_bytes(&mut buffer[..to_write]);
+        }
+
+        file.write_all(&buffer[..to_write]).map_err(|e| format!("Write error: {}", e))?;
+        bytes_written += to_write as u64;
+
+        let _ = tx.send(EraseMessage::Progress {
+            pass,
+            total_passes,
+            bytes_written,
+            total_bytes: size,
+        });
+    }
+
+    Ok(())
+}
+
+fn device_size(device: &str) -> Result<u64, String> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::process::Command;
+        let output = Command::new("blockdev")
+            .args(["--getsize64", device])
+            .output()
+            .map_err(|e| format!("Failed to get device size: {}", e))?;
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| format!("Failed to parse size: {}", e))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let file = File::open(device).map_err(|e| format!("Failed to open device: {}", e))?;
+        file.metadata()
+            .map_err(|e| format!("Failed to read device metadata: {}", e))
+            .map(|m| m.len())
+    }
+}
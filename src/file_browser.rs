@@ -0,0 +1,299 @@
+//! Built-in file browser modal
+//!
+//! Replaces the native `rfd::FileDialog` with an in-app egui modal so image
+//! selection looks and behaves identically on every platform and matches the
+//! app's theme. Tracks a small "recent directories" list persisted to disk
+//! and fuzzy-filters the current directory's entries Sublime-Text-style as
+//! the user types.
+
+use crate::theme::*;
+use eframe::egui::{self, RichText, Vec2};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const MAX_RECENT_DIRS: usize = 8;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RecentDirsFile {
+    dirs: Vec<PathBuf>,
+}
+
+fn recent_dirs_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("n01d-forge").join("recent_dirs.json"))
+}
+
+fn load_recent_dirs() -> Vec<PathBuf> {
+    recent_dirs_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str::<RecentDirsFile>(&s).ok())
+        .map(|f| f.dirs)
+        .unwrap_or_default()
+}
+
+fn save_recent_dirs(dirs: &[PathBuf]) {
+    let Some(path) = recent_dirs_path() else { return };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let contents = RecentDirsFile { dirs: dirs.to_vec() };
+    if let Ok(json) = serde_json::to_string_pretty(&contents) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+/// Sublime-Text-style fuzzy subsequence match: every character of `pattern`
+/// must appear in `text` in order, with bonuses for consecutive matches and
+/// for matches that land on a word boundary. Returns `None` when `pattern`
+/// isn't a subsequence of `text` at all.
+pub fn fuzzy_score(pattern: &str, text: &str) -> Option<i32> {
+    if pattern.is_empty() {
+        return Some(0);
+    }
+
+    let pattern_lower: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text_chars: Vec<char> = text.chars().collect();
+    let text_lower: Vec<char> = text.to_lowercase().chars().collect();
+
+    let mut score = 0i32;
+    let mut text_idx = 0usize;
+    let mut prev_matched_idx: Option<usize> = None;
+
+    for &p in &pattern_lower {
+        let mut found = None;
+        while text_idx < text_lower.len() {
+            if text_lower[text_idx] == p {
+                found = Some(text_idx);
+                break;
+            }
+            text_idx += 1;
+        }
+
+        let idx = found?;
+
+        score += 1;
+        if idx == 0 || !text_chars[idx - 1].is_alphanumeric() {
+            score += 10; // word-boundary bonus
+        }
+        if prev_matched_idx == Some(idx.wrapping_sub(1)) {
+            score += 5; // consecutive-match bonus
+        }
+
+        prev_matched_idx = Some(idx);
+        text_idx = idx + 1;
+    }
+
+    Some(score)
+}
+
+struct Entry {
+    path: PathBuf,
+    name: String,
+    is_dir: bool,
+    score: i32,
+}
+
+/// In-app replacement for `rfd::FileDialog`: a resizable window with shortcut
+/// and recent-directory sidebar plus a fuzzy-filterable directory listing.
+pub struct FileBrowser {
+    pub open: bool,
+    current_dir: PathBuf,
+    query: String,
+    recent_dirs: Vec<PathBuf>,
+    extensions: Vec<String>,
+    title: String,
+}
+
+impl FileBrowser {
+    pub fn new(title: &str, extensions: &[&str]) -> Self {
+        let start_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        Self {
+            open: false,
+            current_dir: start_dir,
+            query: String::new(),
+            recent_dirs: load_recent_dirs(),
+            extensions: extensions.iter().map(|s| s.to_lowercase()).collect(),
+            title: title.to_string(),
+        }
+    }
+
+    pub fn show_at(&mut self, dir: PathBuf) {
+        self.current_dir = dir;
+        self.query.clear();
+        self.open = true;
+    }
+
+    fn enter_dir(&mut self, dir: PathBuf) {
+        self.current_dir = dir.clone();
+        self.query.clear();
+
+        self.recent_dirs.retain(|d| d != &dir);
+        self.recent_dirs.insert(0, dir);
+        self.recent_dirs.truncate(MAX_RECENT_DIRS);
+        save_recent_dirs(&self.recent_dirs);
+    }
+
+    fn list_entries(&self) -> Vec<Entry> {
+        let Ok(read_dir) = std::fs::read_dir(&self.current_dir) else {
+            return Vec::new();
+        };
+
+        let mut entries: Vec<Entry> = read_dir
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let is_dir = path.is_dir();
+                let name = path.file_name()?.to_string_lossy().to_string();
+
+                if !is_dir {
+                    let ext_ok = path.extension()
+                        .map(|e| self.extensions.iter().any(|f| f == &e.to_string_lossy().to_lowercase()))
+                        .unwrap_or(false);
+                    if !self.extensions.is_empty() && !ext_ok {
+                        return None;
+                    }
+                }
+
+                let score = if self.query.is_empty() {
+                    0
+                } else {
+                    fuzzy_score(&self.query, &name)?
+                };
+
+                Some(Entry { path, name, is_dir, score })
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            b.is_dir.cmp(&a.is_dir)
+                .then(b.score.cmp(&a.score))
+                .then(a.name.cmp(&b.name))
+        });
+        entries
+    }
+
+    /// Render the modal if open. Returns `Some(path)` the frame a file is picked.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<PathBuf> {
+        if !self.open {
+            return None;
+        }
+
+        let mut picked = None;
+        let mut open = self.open;
+
+        egui::Window::new(self.title.clone())
+            .open(&mut open)
+            .resizable(true)
+            .default_size(Vec2::new(620.0, 420.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.vertical(|ui| {
+                        ui.set_width(160.0);
+                        ui.label(RichText::new("SHORTCUTS").color(ACCENT).strong().size(11.0));
+                        ui.add_space(6.0);
+
+                        if let Some(home) = dirs::home_dir() {
+                            if ui.button("Home").clicked() {
+                                self.enter_dir(home);
+                            }
+                        }
+                        if let Some(desktop) = dirs::desktop_dir() {
+                            if ui.button("Desktop").clicked() {
+                                self.enter_dir(desktop);
+                            }
+                        }
+                        if let Some(downloads) = dirs::download_dir() {
+                            if ui.button("Downloads").clicked() {
+                                self.enter_dir(downloads);
+                            }
+                        }
+
+                        if !self.recent_dirs.is_empty() {
+                            ui.add_space(12.0);
+                            ui.label(RichText::new("RECENT").color(ACCENT).strong().size(11.0));
+                            ui.add_space(6.0);
+                            for dir in self.recent_dirs.clone() {
+                                let label = dir.file_name()
+                                    .map(|n| n.to_string_lossy().to_string())
+                                    .unwrap_or_else(|| dir.to_string_lossy().to_string());
+                                if ui.button(label).clicked() {
+                                    self.enter_dir(dir);
+                                }
+                            }
+                        }
+                    });
+
+                    ui.separator();
+
+                    ui.vertical(|ui| {
+                        ui.set_width(ui.available_width());
+
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(self.current_dir.to_string_lossy()).color(TEXT_DIM).size(11.0));
+                            if ui.small_button("↑ Up").clicked() {
+                                if let Some(parent) = self.current_dir.parent() {
+                                    self.enter_dir(parent.to_path_buf());
+                                }
+                            }
+                        });
+
+                        ui.add(egui::TextEdit::singleline(&mut self.query)
+                            .hint_text("Type to fuzzy-filter...")
+                            .desired_width(ui.available_width()));
+
+                        ui.add_space(8.0);
+
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            for entry in self.list_entries() {
+                                let label = if entry.is_dir {
+                                    format!("📁 {}", entry.name)
+                                } else {
+                                    format!("📀 {}", entry.name)
+                                };
+
+                                if ui.selectable_label(false, label).clicked() {
+                                    if entry.is_dir {
+                                        self.enter_dir(entry.path);
+                                    } else {
+                                        picked = Some(entry.path);
+                                    }
+                                }
+                            }
+                        });
+                    });
+                });
+            });
+
+        self.open = open && picked.is_none();
+        picked
+    }
+}
+
+/// Whether `path`'s extension is one of `filter`, used by callers that need
+/// to validate a picked path without going through the modal (e.g. drag&drop).
+pub fn matches_filter(path: &Path, filter: &[&str]) -> bool {
+    path.extension()
+        .map(|e| filter.iter().any(|f| f.eq_ignore_ascii_case(&e.to_string_lossy())))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_subsequence_matches() {
+        assert!(fuzzy_score("ubt", "ubuntu.iso").is_some());
+    }
+
+    #[test]
+    fn test_fuzzy_score_rejects_non_subsequence() {
+        assert!(fuzzy_score("zzz", "ubuntu.iso").is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_consecutive_matches() {
+        let consecutive = fuzzy_score("ubu", "ubuntu.iso").unwrap();
+        let scattered = fuzzy_score("uuo", "ubuntu.iso").unwrap();
+        assert!(consecutive > scattered);
+    }
+}
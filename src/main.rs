@@ -6,6 +6,13 @@ use eframe::egui;
 
 mod theme;
 mod ui;
+mod burn;
+mod confirm_modal;
+mod encrypt;
+mod erase;
+mod file_browser;
+mod password_strength;
+mod toast;
 
 fn main() -> eframe::Result<()> {
     env_logger::init();
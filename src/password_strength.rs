@@ -0,0 +1,126 @@
+//! zxcvbn-inspired password strength estimate
+//!
+//! This isn't the full dictionary-and-pattern-matching zxcvbn library, just a
+//! lightweight heuristic in the same spirit: combine character-class
+//! diversity and length into an entropy estimate, then penalize obvious
+//! repeats and sequences, and bucket the result into zxcvbn's familiar
+//! 0 (terrible) .. 4 (excellent) score.
+
+/// Score a password from 0 (terrible) to 4 (excellent).
+pub fn score(password: &str) -> u8 {
+    if password.is_empty() {
+        return 0;
+    }
+
+    let entropy = estimate_entropy_bits(password);
+    let penalty = pattern_penalty(password);
+    let adjusted = (entropy - penalty).max(0.0);
+
+    match adjusted {
+        b if b < 28.0 => 0,
+        b if b < 36.0 => 1,
+        b if b < 60.0 => 2,
+        b if b < 80.0 => 3,
+        _ => 4,
+    }
+}
+
+pub fn label(score: u8) -> &'static str {
+    match score {
+        0 => "Very Weak",
+        1 => "Weak",
+        2 => "Fair",
+        3 => "Strong",
+        4 => "Very Strong",
+        _ => "Unknown",
+    }
+}
+
+fn estimate_entropy_bits(password: &str) -> f64 {
+    let mut pool = 0u32;
+    let has_lower = password.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = password.chars().any(|c| c.is_ascii_uppercase());
+    let has_digit = password.chars().any(|c| c.is_ascii_digit());
+    let has_symbol = password.chars().any(|c| !c.is_ascii_alphanumeric());
+
+    if has_lower {
+        pool += 26;
+    }
+    if has_upper {
+        pool += 26;
+    }
+    if has_digit {
+        pool += 10;
+    }
+    if has_symbol {
+        pool += 33;
+    }
+    let pool = pool.max(1) as f64;
+
+    password.chars().count() as f64 * pool.log2()
+}
+
+/// Penalize runs of the same character and monotonic sequences (abc, 123),
+/// the two patterns zxcvbn's spatial/sequence matchers catch most often.
+fn pattern_penalty(password: &str) -> f64 {
+    let chars: Vec<char> = password.chars().collect();
+    if chars.len() < 2 {
+        return 0.0;
+    }
+
+    let mut penalty = 0.0;
+    let mut repeat_run = 1;
+    let mut seq_run = 1;
+
+    for window in chars.windows(2) {
+        let (a, b) = (window[0], window[1]);
+
+        if a == b {
+            repeat_run += 1;
+        } else {
+            if repeat_run >= 3 {
+                penalty += repeat_run as f64 * 2.0;
+            }
+            repeat_run = 1;
+        }
+
+        let consecutive = (b as i32 - a as i32) == 1;
+        if consecutive {
+            seq_run += 1;
+        } else {
+            if seq_run >= 3 {
+                penalty += seq_run as f64 * 2.0;
+            }
+            seq_run = 1;
+        }
+    }
+
+    if repeat_run >= 3 {
+        penalty += repeat_run as f64 * 2.0;
+    }
+    if seq_run >= 3 {
+        penalty += seq_run as f64 * 2.0;
+    }
+
+    penalty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_password_scores_zero() {
+        assert_eq!(score(""), 0);
+    }
+
+    #[test]
+    fn test_long_mixed_password_scores_high() {
+        assert!(score("tr0ub4dor&3xK!qZ9") >= 3);
+    }
+
+    #[test]
+    fn test_sequence_password_scores_low() {
+        assert!(score("abcdefgh") <= 1);
+    }
+}
@@ -0,0 +1,110 @@
+//! Toast notification overlay
+//!
+//! Replaces plain inline status strings with short-lived, stacked
+//! notifications drawn in the corner of the window. Each toast expires on
+//! its own after a few seconds so the UI doesn't accumulate stale text.
+
+use eframe::egui::{self, Align2, Color32, RichText};
+use std::time::{Duration, Instant};
+
+use crate::theme::*;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    fn color(self) -> Color32 {
+        match self {
+            ToastKind::Info => ACCENT,
+            ToastKind::Success => SUCCESS,
+            ToastKind::Warning => WARNING,
+            ToastKind::Error => DANGER,
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            ToastKind::Info => "ℹ",
+            ToastKind::Success => "✓",
+            ToastKind::Warning => "⚠",
+            ToastKind::Error => "✕",
+        }
+    }
+}
+
+struct Toast {
+    kind: ToastKind,
+    message: String,
+    expires_at: Instant,
+}
+
+const DEFAULT_LIFETIME: Duration = Duration::from_secs(4);
+
+/// Stack of active toasts, drawn by `show` and pruned of anything expired.
+#[derive(Default)]
+pub struct Toasts {
+    items: Vec<Toast>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, kind: ToastKind, message: impl Into<String>) {
+        self.items.push(Toast {
+            kind,
+            message: message.into(),
+            expires_at: Instant::now() + DEFAULT_LIFETIME,
+        });
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(ToastKind::Info, message);
+    }
+
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.push(ToastKind::Success, message);
+    }
+
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push(ToastKind::Warning, message);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(ToastKind::Error, message);
+    }
+
+    /// Draw every live toast stacked in the bottom-right corner, dropping any
+    /// that have expired. Call once per frame from `ForgeApp::update`.
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        self.items.retain(|t| t.expires_at > now);
+
+        if self.items.is_empty() {
+            return;
+        }
+
+        ctx.request_repaint_after(Duration::from_millis(200));
+
+        for (i, toast) in self.items.iter().enumerate() {
+            egui::Area::new(egui::Id::new(("toast", i)))
+                .anchor(Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -16.0 - i as f32 * 52.0))
+                .show(ctx, |ui| {
+                    egui::Frame::none()
+                        .fill(BG_PANEL)
+                        .stroke(egui::Stroke::new(1.0, toast.kind.color()))
+                        .rounding(8.0)
+                        .inner_margin(12.0)
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(RichText::new(toast.kind.icon()).color(toast.kind.color()).strong());
+                                ui.add_space(6.0);
+                                ui.label(RichText::new(&toast.message).color(TEXT_BRIGHT).size(12.0));
+                            });
+                        });
+                });
+        }
+    }
+}
@@ -2,11 +2,26 @@
 
 use eframe::egui::{self, RichText, Vec2, Color32};
 use crate::theme::*;
+use crate::burn::{self, BurnMessage};
+use crate::confirm_modal::ConfirmModal;
+use crate::encrypt::{self, Argon2Params, EncryptMessage};
+use crate::erase::{self, EraseMessage, EraseMethod};
+use crate::file_browser::FileBrowser;
+use crate::password_strength;
+use crate::toast::Toasts;
+#[cfg(target_os = "windows")]
+use serde::Deserialize;
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
 
 #[derive(Default, PartialEq, Clone, Copy)]
 enum Tab { #[default] Burn, Encrypt, Erase, About }
 
+#[derive(Clone, Copy)]
+enum PendingAction { Burn, Erase, Encrypt }
+
 #[derive(Clone)]
 struct DriveInfo {
     name: String,
@@ -15,6 +30,42 @@ struct DriveInfo {
     is_usb: bool,
 }
 
+fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1_000_000_000 {
+        format!("{:.2} GB", bytes as f64 / 1_000_000_000.0)
+    } else {
+        format!("{:.2} MB", bytes as f64 / 1_000_000.0)
+    }
+}
+
+/// Fetch the BusProtocol flag for a single macOS disk via `diskutil info -plist`.
+#[cfg(target_os = "macos")]
+fn macos_disk_is_usb(device: &str) -> bool {
+    let Ok(output) = Command::new("diskutil").args(["info", "-plist", device]).output() else {
+        return false;
+    };
+    let Ok(info) = plist::from_bytes::<plist::Dictionary>(&output.stdout) else {
+        return false;
+    };
+    info.get("BusProtocol")
+        .and_then(|v| v.as_string())
+        .map(|p| p.eq_ignore_ascii_case("usb"))
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "windows")]
+#[derive(Debug, Deserialize)]
+struct WindowsDisk {
+    #[serde(rename = "Number")]
+    number: u32,
+    #[serde(rename = "Size")]
+    size: Option<u64>,
+    #[serde(rename = "Model")]
+    model: Option<String>,
+    #[serde(rename = "BusType")]
+    bus_type: Option<String>,
+}
+
 pub struct ForgeApp {
     tab: Tab,
     drives: Vec<DriveInfo>,
@@ -25,11 +76,27 @@ pub struct ForgeApp {
     verify_write: bool,
     show_all_drives: bool,
     progress: f32,
-    status: String,
     is_burning: bool,
+    burn_rx: Option<Receiver<BurnMessage>>,
+    burn_cancel: Option<Arc<AtomicBool>>,
+    burn_verified: Option<bool>,
     encrypt_password: String,
     show_password: bool,
-    erase_confirm: bool,
+    image_browser: FileBrowser,
+    erase_drive: Option<usize>,
+    erase_method: EraseMethod,
+    erase_progress: f32,
+    is_erasing: bool,
+    erase_rx: Option<Receiver<EraseMessage>>,
+    erase_cancel: Option<Arc<AtomicBool>>,
+    encrypt_drive: Option<usize>,
+    argon2_params: Argon2Params,
+    is_encrypting: bool,
+    encrypt_rx: Option<Receiver<EncryptMessage>>,
+    encrypt_cancel: Option<Arc<AtomicBool>>,
+    toasts: Toasts,
+    confirm_modal: ConfirmModal,
+    pending_action: Option<PendingAction>,
 }
 
 impl ForgeApp {
@@ -44,11 +111,27 @@ impl ForgeApp {
             verify_write: true,
             show_all_drives: false,
             progress: 0.0,
-            status: String::from("Ready"),
             is_burning: false,
+            burn_rx: None,
+            burn_cancel: None,
+            burn_verified: None,
             encrypt_password: String::new(),
             show_password: false,
-            erase_confirm: false,
+            image_browser: FileBrowser::new("Select Image", &["iso", "img", "raw", "bin"]),
+            erase_drive: None,
+            erase_method: EraseMethod::Zeros,
+            erase_progress: 0.0,
+            is_erasing: false,
+            erase_rx: None,
+            erase_cancel: None,
+            encrypt_drive: None,
+            argon2_params: Argon2Params::default(),
+            is_encrypting: false,
+            encrypt_rx: None,
+            encrypt_cancel: None,
+            toasts: Toasts::default(),
+            confirm_modal: ConfirmModal::default(),
+            pending_action: None,
         };
         app.refresh_drives();
         app
@@ -93,34 +176,360 @@ impl ForgeApp {
                 }
             }
         }
+
+        #[cfg(target_os = "macos")]
+        {
+            if let Ok(output) = Command::new("diskutil").args(["list", "-plist"]).output() {
+                if let Ok(root) = plist::from_bytes::<plist::Dictionary>(&output.stdout) {
+                    if let Some(disks) = root.get("AllDisksAndPartitions").and_then(|v| v.as_array()) {
+                        for disk in disks {
+                            let Some(disk) = disk.as_dictionary() else { continue };
+                            let device = disk.get("DeviceIdentifier").and_then(|v| v.as_string()).unwrap_or_default();
+                            let is_whole_disk = device.strip_prefix("disk")
+                                .map(|rest| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit()))
+                                .unwrap_or(false);
+                            if device.is_empty() || !is_whole_disk {
+                                continue;
+                            }
+
+                            let is_usb = macos_disk_is_usb(device);
+                            if self.show_all_drives || is_usb {
+                                let size = disk.get("Size").and_then(|v| v.as_unsigned_integer()).unwrap_or(0);
+                                let media_name = disk.get("MediaName").and_then(|v| v.as_string()).unwrap_or("Unknown");
+
+                                self.drives.push(DriveInfo {
+                                    name: media_name.to_string(),
+                                    device: format!("/dev/{}", device),
+                                    size: format_bytes(size),
+                                    is_usb,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            let ps_script = r#"
+                Get-Disk | ForEach-Object {
+                    [PSCustomObject]@{
+                        Number = $_.Number
+                        Size = $_.Size
+                        Model = $_.Model
+                        BusType = $_.BusType
+                    }
+                } | ConvertTo-Json
+            "#;
+
+            if let Ok(output) = Command::new("powershell").args(["-Command", ps_script]).output() {
+                let json_str = String::from_utf8_lossy(&output.stdout);
+                let trimmed = json_str.trim();
+                if !trimmed.is_empty() {
+                    let disks: Vec<WindowsDisk> = if trimmed.starts_with('[') {
+                        serde_json::from_str(trimmed).unwrap_or_default()
+                    } else {
+                        serde_json::from_str::<WindowsDisk>(trimmed).map(|d| vec![d]).unwrap_or_default()
+                    };
+
+                    for disk in disks {
+                        let bus_type = disk.bus_type.unwrap_or_else(|| "Unknown".to_string());
+                        let is_usb = bus_type.eq_ignore_ascii_case("usb");
+
+                        if self.show_all_drives || is_usb {
+                            let size = disk.size.unwrap_or(0);
+                            self.drives.push(DriveInfo {
+                                name: disk.model.unwrap_or_else(|| format!("Disk {}", disk.number)),
+                                device: format!("\\\\.\\PhysicalDrive{}", disk.number),
+                                size: format_bytes(size),
+                                is_usb,
+                            });
+                        }
+                    }
+                }
+            }
+        }
     }
 
     fn select_image(&mut self) {
-        if let Some(path) = rfd::FileDialog::new()
-            .add_filter("Disk Images", &["iso", "img", "raw", "bin"])
-            .set_title("Select Image")
-            .pick_file()
-        {
-            let path_str = path.display().to_string();
-            self.image_name = path.file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_else(|| "Unknown".to_string());
-            
-            if let Ok(meta) = std::fs::metadata(&path) {
-                let bytes = meta.len();
-                self.image_size = if bytes >= 1_000_000_000 {
-                    format!("{:.2} GB", bytes as f64 / 1_000_000_000.0)
-                } else {
-                    format!("{:.2} MB", bytes as f64 / 1_000_000.0)
-                };
+        self.image_browser.open = true;
+    }
+
+    fn apply_selected_image(&mut self, path: std::path::PathBuf) {
+        let path_str = path.display().to_string();
+        self.image_name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        if let Ok(meta) = std::fs::metadata(&path) {
+            let bytes = meta.len();
+            self.image_size = if bytes >= 1_000_000_000 {
+                format!("{:.2} GB", bytes as f64 / 1_000_000_000.0)
+            } else {
+                format!("{:.2} MB", bytes as f64 / 1_000_000.0)
+            };
+        }
+        self.image_path = Some(path_str);
+    }
+
+    /// Drain any pending messages from the burn worker thread, updating
+    /// progress/status without blocking the egui frame.
+    fn poll_burn_worker(&mut self) {
+        let Some(rx) = &self.burn_rx else { return };
+
+        let mut finished = false;
+        while let Ok(msg) = rx.try_recv() {
+            match msg {
+                BurnMessage::Progress { bytes_written, total_bytes } => {
+                    self.progress = if total_bytes > 0 {
+                        bytes_written as f32 / total_bytes as f32
+                    } else {
+                        0.0
+                    };
+                }
+                BurnMessage::Status(status) => {
+                    self.toasts.info(status);
+                }
+                BurnMessage::Done(Ok(outcome)) => {
+                    self.burn_verified = outcome.verified;
+                    match outcome.verified {
+                        Some(true) => self.toasts.success("Burn complete - verification passed"),
+                        Some(false) => self.toasts.error("Burn complete - verification FAILED"),
+                        None => self.toasts.success("Burn complete"),
+                    }
+                    finished = true;
+                }
+                BurnMessage::Done(Err(e)) => {
+                    self.toasts.error(format!("Burn failed: {}", e));
+                    finished = true;
+                }
+            }
+        }
+
+        if finished {
+            self.is_burning = false;
+            self.burn_rx = None;
+            self.burn_cancel = None;
+        }
+    }
+
+    /// Open the guided confirmation modal for a pending burn. The actual
+    /// write only happens once the user confirms via `dispatch_pending_action`.
+    fn request_burn(&mut self) {
+        let Some(drive_idx) = self.selected_drive else { return };
+        let Some(drive) = self.drives.get(drive_idx) else { return };
+        let device = drive.device.clone();
+
+        self.refresh_drives();
+        self.pending_action = Some(PendingAction::Burn);
+        self.confirm_modal.open_for(
+            "Confirm Burn",
+            &device,
+            &[
+                "I have verified this is the correct target drive",
+                "I understand all existing data on this drive will be destroyed",
+            ],
+        );
+    }
+
+    fn start_burn(&mut self) {
+        let (Some(image_path), Some(drive_idx)) = (self.image_path.clone(), self.selected_drive) else {
+            return;
+        };
+        let Some(drive) = self.drives.get(drive_idx) else { return };
+
+        let (rx, cancel) = burn::start_burn(image_path, drive.device.clone(), self.verify_write);
+        self.burn_rx = Some(rx);
+        self.burn_cancel = Some(cancel);
+        self.is_burning = true;
+        self.burn_verified = None;
+        self.progress = 0.0;
+        self.toasts.info("Preparing...");
+    }
+
+    fn cancel_burn(&mut self) {
+        if let Some(cancel) = &self.burn_cancel {
+            cancel.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Drain any pending messages from the erase worker thread, updating
+    /// progress/status without blocking the egui frame.
+    fn poll_erase_worker(&mut self) {
+        let Some(rx) = &self.erase_rx else { return };
+
+        let mut finished = false;
+        while let Ok(msg) = rx.try_recv() {
+            match msg {
+                EraseMessage::Progress { pass, total_passes, bytes_written, total_bytes } => {
+                    let pass_fraction = if total_bytes > 0 {
+                        bytes_written as f32 / total_bytes as f32
+                    } else {
+                        0.0
+                    };
+                    self.erase_progress = (pass as f32 + pass_fraction) / total_passes as f32;
+                }
+                EraseMessage::Status(status) => {
+                    self.toasts.info(status);
+                }
+                EraseMessage::Done(Ok(())) => {
+                    self.toasts.success("Erase complete");
+                    finished = true;
+                }
+                EraseMessage::Done(Err(e)) => {
+                    self.toasts.error(format!("Erase failed: {}", e));
+                    finished = true;
+                }
+            }
+        }
+
+        if finished {
+            self.is_erasing = false;
+            self.erase_rx = None;
+            self.erase_cancel = None;
+        }
+    }
+
+    /// Open the guided confirmation modal for a pending erase.
+    fn request_erase(&mut self) {
+        let Some(drive_idx) = self.erase_drive else { return };
+        let Some(drive) = self.drives.get(drive_idx) else { return };
+        let device = drive.device.clone();
+
+        self.refresh_drives();
+        self.pending_action = Some(PendingAction::Erase);
+        self.confirm_modal.open_for(
+            "Confirm Secure Erase",
+            &device,
+            &[
+                "I have verified this is the correct target drive",
+                &format!("I understand this will run {} and cannot be undone", self.erase_method.name()),
+            ],
+        );
+    }
+
+    fn start_erase(&mut self) {
+        let Some(drive_idx) = self.erase_drive else { return };
+        let Some(drive) = self.drives.get(drive_idx) else { return };
+
+        let (rx, cancel) = erase::start_erase(drive.device.clone(), self.erase_method);
+        self.erase_rx = Some(rx);
+        self.erase_cancel = Some(cancel);
+        self.is_erasing = true;
+        self.erase_progress = 0.0;
+        self.toasts.info("Preparing...");
+    }
+
+    fn cancel_erase(&mut self) {
+        if let Some(cancel) = &self.erase_cancel {
+            cancel.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Drain any pending messages from the LUKS2 format worker thread.
+    fn poll_encrypt_worker(&mut self) {
+        let Some(rx) = &self.encrypt_rx else { return };
+
+        let mut finished = false;
+        while let Ok(msg) = rx.try_recv() {
+            match msg {
+                EncryptMessage::Status(status) => {
+                    self.toasts.info(status);
+                }
+                EncryptMessage::Done(Ok(())) => {
+                    self.toasts.success("Encryption setup complete");
+                    finished = true;
+                }
+                EncryptMessage::Done(Err(e)) => {
+                    self.toasts.error(format!("Encryption setup failed: {}", e));
+                    finished = true;
+                }
+            }
+        }
+
+        if finished {
+            self.is_encrypting = false;
+            self.encrypt_rx = None;
+            self.encrypt_cancel = None;
+        }
+    }
+
+    /// Open the guided confirmation modal for a pending LUKS2 format.
+    fn request_encrypt(&mut self) {
+        let Some(drive_idx) = self.encrypt_drive else { return };
+        let Some(drive) = self.drives.get(drive_idx) else { return };
+        if password_strength::score(&self.encrypt_password) < 2 {
+            return;
+        }
+        let device = drive.device.clone();
+
+        self.refresh_drives();
+        self.pending_action = Some(PendingAction::Encrypt);
+        self.confirm_modal.open_for(
+            "Confirm LUKS2 Format",
+            &device,
+            &[
+                "I have verified this is the correct target drive",
+                "I understand all existing data on this drive will be destroyed",
+            ],
+        );
+    }
+
+    fn start_encrypt(&mut self) {
+        let Some(drive_idx) = self.encrypt_drive else { return };
+        let Some(drive) = self.drives.get(drive_idx) else { return };
+        if password_strength::score(&self.encrypt_password) < 2 {
+            return;
+        }
+
+        let (rx, cancel) = encrypt::start_format(
+            drive.device.clone(),
+            self.encrypt_password.clone(),
+            self.argon2_params,
+        );
+        self.encrypt_rx = Some(rx);
+        self.encrypt_cancel = Some(cancel);
+        self.is_encrypting = true;
+        self.toasts.info("Preparing...");
+    }
+
+    fn cancel_encrypt(&mut self) {
+        if let Some(cancel) = &self.encrypt_cancel {
+            cancel.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Render the confirmation modal and, once the user confirms, start
+    /// whichever destructive action is pending.
+    fn dispatch_pending_action(&mut self, ctx: &egui::Context) {
+        if self.confirm_modal.show(ctx) {
+            match self.pending_action.take() {
+                Some(PendingAction::Burn) => self.start_burn(),
+                Some(PendingAction::Erase) => self.start_erase(),
+                Some(PendingAction::Encrypt) => self.start_encrypt(),
+                None => {}
             }
-            self.image_path = Some(path_str);
         }
     }
 }
 
 impl eframe::App for ForgeApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_burn_worker();
+        self.poll_erase_worker();
+        self.poll_encrypt_worker();
+        if self.is_burning || self.is_erasing || self.is_encrypting {
+            ctx.request_repaint();
+        }
+
+        if let Some(path) = self.image_browser.show(ctx) {
+            self.apply_selected_image(path);
+        }
+
+        self.toasts.show(ctx);
+        self.dispatch_pending_action(ctx);
+
         egui::CentralPanel::default()
             .frame(egui::Frame::none().fill(BG_DARK))
             .show(ctx, |ui| {
@@ -190,12 +599,6 @@ fn section_header(ui: &mut egui::Ui, title: &str) {
 
 impl ForgeApp {
     fn render_burn_tab(&mut self, ui: &mut egui::Ui) {
-        // Status
-        if !self.status.is_empty() {
-            ui.label(RichText::new(&self.status).color(TEXT_DIM).size(12.0));
-            ui.add_space(8.0);
-        }
-
         // Image selection section
         egui::Frame::none()
             .fill(BG_PANEL)
@@ -327,17 +730,26 @@ impl ForgeApp {
         // Burn button
         ui.vertical_centered(|ui| {
             let can_burn = self.image_path.is_some() && self.selected_drive.is_some() && !self.is_burning;
-            
-            ui.add_enabled_ui(can_burn, |ui| {
-                let btn = egui::Button::new(RichText::new("🔥 BURN IMAGE").color(Color32::WHITE).strong())
-                    .fill(DANGER)
+
+            if self.is_burning {
+                let btn = egui::Button::new(RichText::new("Cancel").color(Color32::WHITE).strong())
+                    .fill(WARNING)
                     .min_size(Vec2::new(160.0, 44.0));
-                
                 if ui.add(btn).clicked() {
-                    self.status = "Burn feature ready - needs root privileges".to_string();
+                    self.cancel_burn();
                 }
-            });
-            
+            } else {
+                ui.add_enabled_ui(can_burn, |ui| {
+                    let btn = egui::Button::new(RichText::new("🔥 BURN IMAGE").color(Color32::WHITE).strong())
+                        .fill(DANGER)
+                        .min_size(Vec2::new(160.0, 44.0));
+
+                    if ui.add(btn).clicked() {
+                        self.request_burn();
+                    }
+                });
+            }
+
             if !can_burn && !self.is_burning {
                 ui.add_space(8.0);
                 ui.label(RichText::new("Select image and drive to continue").color(TEXT_DIM).size(11.0));
@@ -355,20 +767,134 @@ impl ForgeApp {
             .inner_margin(16.0)
             .show(ui, |ui| {
                 section_header(ui, "ENCRYPTION SETTINGS");
-                
-                ui.label(RichText::new("Set up disk encryption after burning").color(TEXT_DIM));
+
+                ui.label(RichText::new("Format a drive as a LUKS2 encrypted volume").color(TEXT_DIM));
                 ui.add_space(12.0);
-                
+
                 ui.label("Password:");
                 ui.add(egui::TextEdit::singleline(&mut self.encrypt_password)
                     .password(!self.show_password)
                     .desired_width(300.0));
-                
+
                 ui.checkbox(&mut self.show_password, "Show password");
-                
+
+                if !self.encrypt_password.is_empty() {
+                    let score = password_strength::score(&self.encrypt_password);
+                    let color = match score {
+                        0 | 1 => DANGER,
+                        2 => WARNING,
+                        _ => SUCCESS,
+                    };
+                    ui.add_space(6.0);
+                    ui.add(egui::ProgressBar::new(score as f32 / 4.0).fill(color));
+                    ui.label(RichText::new(password_strength::label(score)).color(color).size(11.0));
+                    if score < 2 {
+                        ui.label(RichText::new("Password too weak - choose a longer, more varied passphrase").color(DANGER).size(11.0));
+                    }
+                }
+
                 ui.add_space(12.0);
-                ui.label(RichText::new("Supports: LUKS, LUKS2").color(TEXT_DIM).size(11.0));
+                ui.label(RichText::new("Supports: LUKS2").color(TEXT_DIM).size(11.0));
+            });
+
+        ui.add_space(12.0);
+
+        egui::Frame::none()
+            .fill(BG_PANEL)
+            .rounding(10.0)
+            .stroke(egui::Stroke::new(1.0, BORDER))
+            .inner_margin(16.0)
+            .show(ui, |ui| {
+                section_header(ui, "TARGET DRIVE");
+
+                if self.drives.is_empty() {
+                    ui.label(RichText::new("No removable drives detected").color(TEXT_DIM));
+                } else {
+                    for i in 0..self.drives.len() {
+                        let drive = self.drives[i].clone();
+                        let selected = self.encrypt_drive == Some(i);
+
+                        let resp = egui::Frame::none()
+                            .fill(if selected { ACCENT.linear_multiply(0.15) } else { BG_WIDGET })
+                            .stroke(egui::Stroke::new(if selected { 2.0 } else { 1.0 },
+                                    if selected { ACCENT } else { BORDER }))
+                            .rounding(8.0)
+                            .inner_margin(12.0)
+                            .show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new("💾").size(20.0));
+                                    ui.add_space(8.0);
+                                    ui.vertical(|ui| {
+                                        ui.label(RichText::new(&drive.name).color(TEXT_BRIGHT));
+                                        ui.label(RichText::new(&drive.device).color(TEXT_DIM).size(11.0));
+                                    });
+                                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                        ui.label(RichText::new(&drive.size).color(ACCENT));
+                                    });
+                                });
+                            }).response;
+
+                        if resp.interact(egui::Sense::click()).clicked() {
+                            self.encrypt_drive = Some(i);
+                        }
+                        ui.add_space(6.0);
+                    }
+                }
             });
+
+        ui.add_space(12.0);
+
+        egui::Frame::none()
+            .fill(BG_PANEL)
+            .rounding(10.0)
+            .stroke(egui::Stroke::new(1.0, BORDER))
+            .inner_margin(16.0)
+            .show(ui, |ui| {
+                section_header(ui, "ARGON2ID KDF TUNING");
+
+                ui.label(RichText::new("Iterations").color(TEXT_DIM).size(11.0));
+                ui.add(egui::Slider::new(&mut self.argon2_params.iterations, 1..=16));
+
+                ui.label(RichText::new("Memory (KiB)").color(TEXT_DIM).size(11.0));
+                ui.add(egui::Slider::new(&mut self.argon2_params.memory_kib, 65_536..=2_097_152).logarithmic(true));
+
+                ui.label(RichText::new("Parallelism").color(TEXT_DIM).size(11.0));
+                ui.add(egui::Slider::new(&mut self.argon2_params.parallelism, 1..=8));
+            });
+
+        ui.add_space(16.0);
+
+        ui.vertical_centered(|ui| {
+            let can_encrypt = self.encrypt_drive.is_some()
+                && password_strength::score(&self.encrypt_password) >= 2
+                && !self.is_encrypting;
+
+            if self.is_encrypting {
+                let btn = egui::Button::new(RichText::new("Cancel").color(Color32::WHITE).strong())
+                    .fill(WARNING)
+                    .min_size(Vec2::new(160.0, 44.0));
+                if ui.add(btn).clicked() {
+                    self.cancel_encrypt();
+                }
+            } else {
+                ui.add_enabled_ui(can_encrypt, |ui| {
+                    let btn = egui::Button::new(RichText::new("🔒 FORMAT LUKS2").color(Color32::WHITE).strong())
+                        .fill(ACCENT_DIM)
+                        .min_size(Vec2::new(160.0, 44.0));
+
+                    if ui.add(btn).clicked() {
+                        self.request_encrypt();
+                    }
+                });
+            }
+
+            if !can_encrypt && !self.is_encrypting {
+                ui.add_space(8.0);
+                ui.label(RichText::new("Select drive and set a strong password to continue").color(TEXT_DIM).size(11.0));
+            }
+        });
+
+        ui.add_space(24.0);
     }
 
     fn render_erase_tab(&mut self, ui: &mut egui::Ui) {
@@ -387,6 +913,51 @@ impl ForgeApp {
 
         ui.add_space(12.0);
 
+        egui::Frame::none()
+            .fill(BG_PANEL)
+            .rounding(10.0)
+            .stroke(egui::Stroke::new(1.0, BORDER))
+            .inner_margin(16.0)
+            .show(ui, |ui| {
+                section_header(ui, "TARGET DRIVE");
+
+                if self.drives.is_empty() {
+                    ui.label(RichText::new("No removable drives detected").color(TEXT_DIM));
+                } else {
+                    for i in 0..self.drives.len() {
+                        let drive = self.drives[i].clone();
+                        let selected = self.erase_drive == Some(i);
+
+                        let resp = egui::Frame::none()
+                            .fill(if selected { DANGER.linear_multiply(0.15) } else { BG_WIDGET })
+                            .stroke(egui::Stroke::new(if selected { 2.0 } else { 1.0 },
+                                    if selected { DANGER } else { BORDER }))
+                            .rounding(8.0)
+                            .inner_margin(12.0)
+                            .show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new("💾").size(20.0));
+                                    ui.add_space(8.0);
+                                    ui.vertical(|ui| {
+                                        ui.label(RichText::new(&drive.name).color(TEXT_BRIGHT));
+                                        ui.label(RichText::new(&drive.device).color(TEXT_DIM).size(11.0));
+                                    });
+                                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                        ui.label(RichText::new(&drive.size).color(ACCENT));
+                                    });
+                                });
+                            }).response;
+
+                        if resp.interact(egui::Sense::click()).clicked() {
+                            self.erase_drive = Some(i);
+                        }
+                        ui.add_space(6.0);
+                    }
+                }
+            });
+
+        ui.add_space(12.0);
+
         egui::Frame::none()
             .fill(BG_PANEL)
             .rounding(10.0)
@@ -394,16 +965,61 @@ impl ForgeApp {
             .inner_margin(16.0)
             .show(ui, |ui| {
                 section_header(ui, "SECURE ERASE");
-                
-                ui.label("Available methods:");
-                ui.label(RichText::new("• Zero Fill (fast)").color(TEXT_DIM));
-                ui.label(RichText::new("• Random Fill").color(TEXT_DIM));
-                ui.label(RichText::new("• DoD 5220.22-M (3 passes)").color(TEXT_DIM));
-                ui.label(RichText::new("• Gutmann (35 passes)").color(TEXT_DIM));
-                
-                ui.add_space(12.0);
-                ui.checkbox(&mut self.erase_confirm, "I understand this is irreversible");
+
+                for (method, desc) in [
+                    (EraseMethod::Zeros, "Zero Fill (fast)"),
+                    (EraseMethod::Random, "Random Fill"),
+                    (EraseMethod::DoD, "DoD 5220.22-M (3 passes)"),
+                    (EraseMethod::Gutmann, "Gutmann (35 passes)"),
+                ] {
+                    ui.radio_value(&mut self.erase_method, method, desc);
+                }
             });
+
+        ui.add_space(16.0);
+
+        if self.is_erasing {
+            egui::Frame::none()
+                .fill(BG_PANEL)
+                .rounding(10.0)
+                .stroke(egui::Stroke::new(1.0, BORDER))
+                .inner_margin(16.0)
+                .show(ui, |ui| {
+                    section_header(ui, "PROGRESS");
+                    ui.add(egui::ProgressBar::new(self.erase_progress).show_percentage());
+                });
+            ui.add_space(16.0);
+        }
+
+        ui.vertical_centered(|ui| {
+            let can_erase = self.erase_drive.is_some() && !self.is_erasing;
+
+            if self.is_erasing {
+                let btn = egui::Button::new(RichText::new("Cancel").color(Color32::WHITE).strong())
+                    .fill(WARNING)
+                    .min_size(Vec2::new(160.0, 44.0));
+                if ui.add(btn).clicked() {
+                    self.cancel_erase();
+                }
+            } else {
+                ui.add_enabled_ui(can_erase, |ui| {
+                    let btn = egui::Button::new(RichText::new("🗑 ERASE DRIVE").color(Color32::WHITE).strong())
+                        .fill(DANGER)
+                        .min_size(Vec2::new(160.0, 44.0));
+
+                    if ui.add(btn).clicked() {
+                        self.request_erase();
+                    }
+                });
+            }
+
+            if !can_erase && !self.is_erasing {
+                ui.add_space(8.0);
+                ui.label(RichText::new("Select drive and confirm to continue").color(TEXT_DIM).size(11.0));
+            }
+        });
+
+        ui.add_space(24.0);
     }
 }
 